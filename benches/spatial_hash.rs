@@ -0,0 +1,155 @@
+//! Compares the spatial hash used by the game's proximity queries against brute-force scans.
+//!
+//! Run with `cargo bench --bench spatial_hash`.
+
+use bevy::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+#[allow(dead_code)]
+#[path = "../src/game/spatial_hash.rs"]
+mod spatial_hash;
+
+use spatial_hash::SpatialHash;
+
+const TILE_SIZE: f32 = 16.;
+const CELL_SIZE: f32 = TILE_SIZE * 4.;
+const QUERY_RADIUS: f32 = TILE_SIZE * 1.5;
+const ENTITY_COUNTS: [u32; 3] = [1_000, 5_000, 20_000];
+
+/// Spreads `count` entities over a level sized so density stays roughly constant.
+fn positions(count: u32) -> Vec<(Entity, Vec2)> {
+    let side = (count as f32).sqrt() * TILE_SIZE * 2.;
+    let mut seed = 0x2545_f491_u32;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / u32::MAX as f32
+    };
+
+    (0..count)
+        .map(|index| {
+            (
+                Entity::from_raw(index),
+                Vec2::new(next() * side, next() * side),
+            )
+        })
+        .collect()
+}
+
+fn build(entities: &[(Entity, Vec2)]) -> SpatialHash {
+    let mut spatial_hash = SpatialHash::new(CELL_SIZE);
+    for &(entity, position) in entities {
+        spatial_hash.insert(entity, position);
+    }
+    spatial_hash
+}
+
+/// What every unit does each tick for separation: look at all neighbors within a radius.
+fn all_neighbors(c: &mut Criterion) {
+    let mut group = c.benchmark_group("all_neighbors");
+
+    for count in ENTITY_COUNTS {
+        let entities = positions(count);
+        let mut spatial_hash = build(&entities);
+
+        group.bench_with_input(
+            BenchmarkId::new("spatial_hash", count),
+            &entities,
+            |b, entities| {
+                b.iter(|| {
+                    spatial_hash.clear();
+                    for &(entity, position) in entities {
+                        spatial_hash.insert(entity, position);
+                    }
+
+                    entities
+                        .iter()
+                        .map(|&(_, position)| {
+                            spatial_hash.query_radius(position, QUERY_RADIUS).count()
+                        })
+                        .sum::<usize>()
+                });
+            },
+        );
+
+        // The quadratic scan is only worth timing while it still finishes in reasonable time.
+        if count <= 5_000 {
+            group.bench_with_input(
+                BenchmarkId::new("brute_force", count),
+                &entities,
+                |b, entities| {
+                    b.iter(|| {
+                        entities
+                            .iter()
+                            .map(|&(_, position)| {
+                                entities
+                                    .iter()
+                                    .filter(|(_, other)| {
+                                        position.distance_squared(*other)
+                                            <= QUERY_RADIUS * QUERY_RADIUS
+                                    })
+                                    .count()
+                            })
+                            .sum::<usize>()
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn nearest_k(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nearest_k");
+
+    for count in ENTITY_COUNTS {
+        let entities = positions(count);
+        let spatial_hash = build(&entities);
+
+        group.bench_with_input(
+            BenchmarkId::new("spatial_hash", count),
+            &entities,
+            |b, entities| {
+                b.iter(|| {
+                    entities
+                        .iter()
+                        .map(|&(_, position)| {
+                            spatial_hash.nearest_k(position, 7, TILE_SIZE * 4.).len()
+                        })
+                        .sum::<usize>()
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn rebuild(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rebuild");
+
+    for count in ENTITY_COUNTS {
+        let entities = positions(count);
+        let mut spatial_hash = build(&entities);
+
+        group.bench_with_input(
+            BenchmarkId::new("spatial_hash", count),
+            &entities,
+            |b, entities| {
+                b.iter(|| {
+                    spatial_hash.clear();
+                    for &(entity, position) in entities {
+                        spatial_hash.insert(entity, black_box(position));
+                    }
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, all_neighbors, nearest_k, rebuild);
+criterion_main!(benches);
@@ -1,69 +1,298 @@
-use bevy::prelude::*;
+use std::{collections::HashMap, f32::consts::FRAC_PI_4, fmt};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use serde::Deserialize;
 
 pub struct AnimationPlugin;
 
 impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, animate_sprite);
-        app.add_systems(
-            PostUpdate,
-            reset_animation_index.run_if(any_match_filter::<Changed<AnimationIndices>>),
-        );
+        app.init_asset::<SpriteAnimationSet>();
+        app.init_asset_loader::<SpriteAnimationSetLoader>();
+        app.add_systems(Update, animate_sprites);
+    }
+}
+
+/// Named clips for one sprite sheet, loaded from an `.anim.ron` file.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct SpriteAnimationSet {
+    clips: HashMap<String, SpriteClip>,
+}
+
+#[derive(Deserialize)]
+struct SpriteClip {
+    /// First and last atlas index of the clip, inclusive.
+    frames: (usize, usize),
+    #[serde(default)]
+    mode: PlaybackMode,
+    /// Seconds each frame is shown, unless overridden in `frame_durations`.
+    frame_seconds: f32,
+    /// Per-frame durations, for clips whose frames don't all hold for the same time.
+    #[serde(default)]
+    frame_durations: Vec<f32>,
+    /// Atlas offset of the clip for each of the 8 directions, counter-clockwise from east.
+    #[serde(default)]
+    directions: Option<[usize; 8]>,
+    /// Clip to switch to once a clip played `Once` has finished.
+    #[serde(default)]
+    next: Option<String>,
+    /// Named events fired when the clip enters a frame.
+    #[serde(default)]
+    events: Vec<(usize, String)>,
+}
+
+#[derive(Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+enum PlaybackMode {
+    #[default]
+    Loop,
+    Once,
+    PingPong,
+}
+
+impl SpriteClip {
+    fn len(&self) -> usize {
+        self.frames.1.saturating_sub(self.frames.0) + 1
+    }
+
+    fn frame_seconds(&self, frame: usize) -> f32 {
+        self.frame_durations
+            .get(frame)
+            .copied()
+            .unwrap_or(self.frame_seconds)
+    }
+
+    fn atlas_index(&self, frame: usize, direction: usize) -> usize {
+        let offset = self
+            .directions
+            .map_or(0, |directions| directions[direction % directions.len()]);
+
+        self.frames.0 + offset + frame
+    }
+
+    /// Returns the frame after `frame` and whether playback is going backwards,
+    /// or `None` once a clip played `Once` is over.
+    fn advance(&self, frame: usize, reversing: bool) -> Option<(usize, bool)> {
+        let last = self.len() - 1;
+
+        match self.mode {
+            PlaybackMode::Loop => Some((if frame >= last { 0 } else { frame + 1 }, false)),
+            PlaybackMode::Once => (frame < last).then_some((frame + 1, false)),
+            PlaybackMode::PingPong if last == 0 => Some((0, false)),
+            PlaybackMode::PingPong if reversing => Some(if frame == 0 {
+                (1, false)
+            } else {
+                (frame - 1, true)
+            }),
+            PlaybackMode::PingPong => Some(if frame >= last {
+                (last - 1, true)
+            } else {
+                (frame + 1, false)
+            }),
+        }
     }
 }
 
+/// Plays clips from a [`SpriteAnimationSet`] on the entity's sprite atlas.
 #[derive(Component)]
-pub struct AnimationIndices {
-    first: usize,
-    last: usize,
+#[require(Sprite)]
+pub struct SpriteAnimation {
+    set: Handle<SpriteAnimationSet>,
+    clip: String,
+    frame: usize,
+    elapsed: f32,
+    reversing: bool,
+    /// A clip played `Once` showed its last frame and has no clip to move on to.
+    finished: bool,
+    /// Clip was changed and starts from its first frame on the next update.
+    pending_start: bool,
+    direction: usize,
+    /// Multiplies every frame duration, so values above 1 slow the animation down.
+    duration_scale: f32,
 }
 
-impl AnimationIndices {
-    pub fn new(first: usize, last: usize) -> Self {
-        Self { first, last }
+impl SpriteAnimation {
+    pub fn new(set: Handle<SpriteAnimationSet>, clip: impl Into<String>) -> Self {
+        Self {
+            set,
+            clip: clip.into(),
+            frame: 0,
+            elapsed: 0.,
+            reversing: false,
+            finished: false,
+            pending_start: true,
+            direction: 0,
+            duration_scale: 1.,
+        }
+    }
+
+    /// Switches to `clip`, unless it is already playing.
+    pub fn play(&mut self, clip: &str) {
+        if self.clip != clip {
+            self.replay(clip);
+        }
+    }
+
+    /// Starts `clip` from its first frame, even if it is already playing.
+    pub fn replay(&mut self, clip: &str) {
+        clip.clone_into(&mut self.clip);
+        self.pending_start = true;
     }
 
-    pub fn first(&self) -> usize {
-        self.first
+    pub fn clip(&self) -> &str {
+        &self.clip
     }
 
-    pub fn last(&self) -> usize {
-        self.last
+    /// Picks which of the 8 drawn directions directional clips play in.
+    pub fn set_direction(&mut self, direction: Dir2) {
+        let angle = direction.y.atan2(direction.x);
+        self.direction = (angle / FRAC_PI_4).round().rem_euclid(8.) as usize;
+    }
+
+    pub fn set_duration_scale(&mut self, duration_scale: f32) {
+        self.duration_scale = duration_scale;
+    }
+
+    fn start(&mut self) {
+        self.frame = 0;
+        self.elapsed = 0.;
+        self.reversing = false;
+        self.finished = false;
+        self.pending_start = false;
     }
 }
 
-#[derive(Component, Deref, DerefMut)]
-pub struct AnimationTimer(Timer);
+/// Triggered on an entity when its animation enters a frame tagged in the clip's `events`.
+#[derive(Event)]
+pub struct AnimationFrameEvent {
+    pub clip: String,
+    pub name: String,
+}
 
-impl AnimationTimer {
-    pub fn from_seconds(secs: f32) -> Self {
-        Self(Timer::from_seconds(secs, TimerMode::Repeating))
+impl AnimationFrameEvent {
+    pub fn new(clip: String, name: String) -> Self {
+        Self { clip, name }
     }
 }
 
-fn animate_sprite(
+fn animate_sprites(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut SpriteAnimation, &mut Sprite)>,
+    animation_sets: Res<Assets<SpriteAnimationSet>>,
     time: Res<Time>,
-    mut query: Query<(&AnimationIndices, &mut AnimationTimer, &mut Sprite)>,
 ) {
-    for (indices, mut timer, mut sprite) in &mut query {
-        if timer.tick(time.delta()).just_finished() {
-            if let Some(ref mut texture_atlas) = sprite.texture_atlas {
-                texture_atlas.index = if texture_atlas.index + 1 > indices.last {
-                    indices.first
-                } else {
-                    texture_atlas.index + 1
-                };
+    for (entity, mut animation, mut sprite) in &mut query {
+        let Some(animation_set) = animation_sets.get(&animation.set) else {
+            continue;
+        };
+
+        let mut entered_frame = if animation.pending_start {
+            animation.start();
+            true
+        } else {
+            animation.elapsed += time.delta_secs();
+            false
+        };
+
+        // Several frames can pass in one update when frames are short or the game hitches.
+        while let Some(clip) = animation_set.clips.get(&animation.clip) {
+            if entered_frame {
+                for (_, name) in clip
+                    .events
+                    .iter()
+                    .filter(|(frame, _)| *frame == animation.frame)
+                {
+                    commands.trigger_targets(
+                        AnimationFrameEvent::new(animation.clip.clone(), name.clone()),
+                        entity,
+                    );
+                }
+            }
+
+            let frame_seconds = clip.frame_seconds(animation.frame) * animation.duration_scale;
+
+            if animation.finished || frame_seconds <= 0. || animation.elapsed < frame_seconds {
+                break;
+            }
+
+            animation.elapsed -= frame_seconds;
+            entered_frame = true;
+
+            match clip.advance(animation.frame, animation.reversing) {
+                Some((frame, reversing)) => {
+                    animation.frame = frame;
+                    animation.reversing = reversing;
+                }
+                None => match &clip.next {
+                    Some(next) => {
+                        animation.clip.clone_from(next);
+                        animation.start();
+                    }
+                    None => {
+                        animation.finished = true;
+                        entered_frame = false;
+                    }
+                },
+            }
+        }
+
+        let Some(clip) = animation_set.clips.get(&animation.clip) else {
+            continue;
+        };
+
+        if let Some(texture_atlas) = sprite.texture_atlas.as_mut() {
+            let index = clip.atlas_index(animation.frame, animation.direction);
+
+            if texture_atlas.index != index {
+                texture_atlas.index = index;
             }
         }
     }
 }
 
-fn reset_animation_index(
-    mut query: Query<(&AnimationIndices, &mut Sprite), Changed<AnimationIndices>>,
-) {
-    for (indices, mut sprite) in &mut query {
-        if let Some(ref mut texture_atlas) = sprite.texture_atlas {
-            texture_atlas.index = indices.first();
+#[derive(Default)]
+struct SpriteAnimationSetLoader;
+
+#[derive(Debug)]
+enum SpriteAnimationSetLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for SpriteAnimationSetLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read animation set: {error}"),
+            Self::Ron(error) => write!(f, "could not parse animation set: {error}"),
         }
     }
 }
+
+impl std::error::Error for SpriteAnimationSetLoaderError {}
+
+impl AssetLoader for SpriteAnimationSetLoader {
+    type Asset = SpriteAnimationSet;
+    type Settings = ();
+    type Error = SpriteAnimationSetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(SpriteAnimationSetLoaderError::Io)?;
+
+        ron::de::from_bytes(&bytes).map_err(SpriteAnimationSetLoaderError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["anim.ron"]
+    }
+}
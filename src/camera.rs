@@ -2,6 +2,7 @@ use bevy::{
     prelude::*,
     render::view::{Layer, RenderLayers},
 };
+use rand::Rng;
 
 use crate::{
     game::Player,
@@ -26,13 +27,17 @@ pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
+        app.add_event::<CameraShakeEvent>();
+        app.init_resource::<CameraShake>();
         app.add_systems(Startup, setup_camera);
 
         app.add_systems(
             PostUpdate,
             (
-                update_camera.run_if(any_with_component::<Player>),
-                constrain_camera_position_to_level.after(update_camera),
+                (update_camera, shake_camera)
+                    .chain()
+                    .run_if(any_with_component::<Player>),
+                constrain_camera_position_to_level.after(shake_camera),
                 y_sorting,
                 inverse_y_sorting,
             ),
@@ -65,6 +70,25 @@ fn sub_layer_camera(layer: Layer) -> impl Bundle {
 )]
 pub struct MainCamera;
 
+/// Shakes the camera, adding `trauma` to whatever shake is already going on.
+#[derive(Event)]
+pub struct CameraShakeEvent {
+    trauma: f32,
+}
+
+impl CameraShakeEvent {
+    /// `trauma` ranges from 0 for no shake to 1 for the strongest shake.
+    pub fn new(trauma: f32) -> Self {
+        Self { trauma }
+    }
+}
+
+/// Current shake strength, decaying back to zero over time.
+#[derive(Resource, Default)]
+pub struct CameraShake {
+    trauma: f32,
+}
+
 #[derive(Component)]
 pub struct YSorted;
 
@@ -93,6 +117,32 @@ fn update_camera(
     camera_transform.translation.y = player_transform.translation.y;
 }
 
+fn shake_camera(
+    mut camera_shake_event_reader: EventReader<CameraShakeEvent>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut camera_transform: Single<&mut Transform, With<MainCamera>>,
+    time: Res<Time>,
+) {
+    const MAX_SHAKE_OFFSET: f32 = TILE_SIZE.x;
+    const TRAUMA_DECAY: f32 = 1.5;
+
+    for camera_shake_event in camera_shake_event_reader.read() {
+        camera_shake.trauma = (camera_shake.trauma + camera_shake_event.trauma).min(1.);
+    }
+
+    if camera_shake.trauma <= 0. {
+        return;
+    }
+
+    // Squaring the trauma makes small hits subtle while big ones still hit hard.
+    let offset_length = MAX_SHAKE_OFFSET * camera_shake.trauma * camera_shake.trauma;
+    let mut rng = rand::rng();
+    let offset = Vec2::new(rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0));
+
+    camera_transform.translation += (offset * offset_length).extend(0.);
+    camera_shake.trauma = (camera_shake.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.);
+}
+
 fn constrain_camera_position_to_level(
     main_camera: Single<(&Camera, &mut Transform), With<MainCamera>>,
 ) {
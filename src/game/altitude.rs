@@ -0,0 +1,180 @@
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_enhanced_input::prelude::*;
+use bevy_rapier2d::prelude::CollisionGroups;
+
+use crate::{
+    camera::RenderLayer,
+    input::{actions::FlyHigh, DefaultInputContext},
+    playing, AppState,
+};
+
+use super::{
+    dragon_ability::Diving, game_timer::GameTimer, scorch::soft_disc_image, Player,
+    HIGH_PROJECTILE_GROUP, PLAYER_GROUP, POWERUP_GROUP, PROJECTILE_GROUP,
+};
+
+/// Altitude gained or lost each second while climbing or coming back down.
+const CLIMB_PER_SECOND: f32 = 1.5;
+/// Altitude lost each second while diving.
+const DIVE_DESCENT_PER_SECOND: f32 = 4.;
+/// Altitude from which only heavy projectiles reach the dragon.
+const HIGH_ALTITUDE: f32 = 0.75;
+/// Altitude below which the dragon is close enough to the ground to fight in melee.
+const LOW_ALTITUDE: f32 = 0.25;
+/// Share of its damage the breath keeps at the highest altitude.
+const HIGH_BREATH_EFFECTIVENESS: f32 = 0.2;
+/// Scale of the dragon at the highest altitude, as it gets closer to the camera.
+const HIGH_SCALE: f32 = 1.35;
+/// How small the dragon gets at the bottom of a dive, as it drops towards the ground.
+const DIVE_SCALE: f32 = 0.75;
+const SHADOW_SIZE: Vec2 = Vec2::new(120., 50.);
+/// Scale of the shadow at the highest altitude.
+const HIGH_SHADOW_SCALE: f32 = 0.45;
+const SHADOW_ALPHA: f32 = 0.4;
+const HIGH_SHADOW_ALPHA: f32 = 0.15;
+/// Distance between the dragon and its shadow at the lowest and highest altitudes.
+const SHADOW_OFFSET: f32 = 20.;
+const HIGH_SHADOW_OFFSET: f32 = 90.;
+
+pub(super) struct AltitudePlugin;
+
+impl Plugin for AltitudePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AltitudeAssets>();
+
+        app.add_systems(
+            FixedUpdate,
+            (change_altitude, apply_altitude).chain().run_if(playing()),
+        );
+        // Moved every frame like the camera, so the shadow doesn't lag behind the dragon.
+        app.add_systems(Update, follow_dragon_with_shadow.run_if(playing()));
+
+        app.add_observer(spawn_dragon_shadow);
+    }
+}
+
+/// How high the dragon flies, from 0 right above the ground to 1 as high as it goes.
+/// Flying high keeps it out of reach of most projectiles, at the cost of a weaker breath and
+/// no melee.
+#[derive(Component, Default, PartialEq)]
+pub struct Altitude(f32);
+
+impl Altitude {
+    pub fn is_high(&self) -> bool {
+        self.0 >= HIGH_ALTITUDE
+    }
+
+    pub fn is_low(&self) -> bool {
+        self.0 <= LOW_ALTITUDE
+    }
+
+    /// Share of its damage the breath keeps at the current altitude.
+    pub fn breath_effectiveness(&self) -> f32 {
+        1.0.lerp(HIGH_BREATH_EFFECTIVENESS, self.0)
+    }
+}
+
+/// Dark patch on the ground under the dragon, smaller and fainter the higher it flies.
+#[derive(Component)]
+struct DragonShadow;
+
+#[derive(Resource)]
+struct AltitudeAssets {
+    shadow_image: Handle<Image>,
+}
+
+impl FromWorld for AltitudeAssets {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            shadow_image: world.resource_mut::<Assets<Image>>().add(soft_disc_image()),
+        }
+    }
+}
+
+fn spawn_dragon_shadow(
+    _trigger: Trigger<OnAdd, Altitude>,
+    mut commands: Commands,
+    altitude_assets: Res<AltitudeAssets>,
+) {
+    commands.spawn((
+        DragonShadow,
+        Sprite {
+            image: altitude_assets.shadow_image.clone(),
+            color: Color::BLACK.with_alpha(SHADOW_ALPHA),
+            custom_size: Some(SHADOW_SIZE),
+            ..default()
+        },
+        // Below every unit on the ground.
+        Transform::from_xyz(0., 0., -2.),
+        RenderLayers::layer(RenderLayer::Ground.into()),
+        StateScoped(AppState::GameOver),
+    ));
+}
+
+/// Climbs while the button is held and comes back down once it is let go.
+/// A dive takes the dragon all the way down, faster.
+fn change_altitude(
+    player: Single<(&Actions<DefaultInputContext>, &mut Altitude, Has<Diving>), With<Player>>,
+    time: Res<Time>,
+) {
+    let (actions, mut altitude, diving) = player.into_inner();
+
+    let (target, rate) = if diving {
+        (0., DIVE_DESCENT_PER_SECOND)
+    } else if actions
+        .state::<FlyHigh>()
+        .is_ok_and(|state| state == ActionState::Fired)
+    {
+        (1., CLIMB_PER_SECOND)
+    } else {
+        (0., CLIMB_PER_SECOND)
+    };
+
+    let step = rate * time.delta_secs();
+    let current = altitude.0;
+    altitude.set_if_neq(Altitude(current + (target - current).clamp(-step, step)));
+}
+
+/// Only projectiles that reach high altitude can hit the dragon while it flies high.
+fn apply_altitude(
+    player: Single<
+        (
+            &Altitude,
+            &mut Transform,
+            &mut CollisionGroups,
+            Option<&GameTimer<Diving>>,
+        ),
+        With<Player>,
+    >,
+) {
+    let (altitude, mut transform, mut collision_groups, dive_timer) = player.into_inner();
+
+    let dive_scale = dive_timer.map_or(1., |timer| 1.0.lerp(DIVE_SCALE, timer.fraction()));
+    transform.scale = Vec3::splat(1.0.lerp(HIGH_SCALE, altitude.0) * dive_scale);
+
+    let reachable_by = if altitude.is_high() {
+        HIGH_PROJECTILE_GROUP
+    } else {
+        PROJECTILE_GROUP
+    };
+    collision_groups.set_if_neq(CollisionGroups::new(
+        PLAYER_GROUP,
+        reachable_by | POWERUP_GROUP,
+    ));
+}
+
+fn follow_dragon_with_shadow(
+    player: Single<(&Transform, &Altitude), With<Player>>,
+    shadow: Single<(&mut Transform, &mut Sprite), (With<DragonShadow>, Without<Player>)>,
+) {
+    let (player_transform, altitude) = player.into_inner();
+    let (mut shadow_transform, mut shadow_sprite) = shadow.into_inner();
+
+    let offset = SHADOW_OFFSET.lerp(HIGH_SHADOW_OFFSET, altitude.0);
+    shadow_transform.translation.x = player_transform.translation.x;
+    shadow_transform.translation.y = player_transform.translation.y - offset;
+    shadow_transform.scale = Vec3::splat(1.0.lerp(HIGH_SHADOW_SCALE, altitude.0));
+    shadow_sprite
+        .color
+        .set_alpha(SHADOW_ALPHA.lerp(HIGH_SHADOW_ALPHA, altitude.0));
+}
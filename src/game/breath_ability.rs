@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_enoki::prelude::Particle2dEffect;
+use bevy_rapier2d::prelude::Collider;
+
+use crate::{
+    animation::SpriteAnimationSet,
+    input::{
+        actions::{FireBreath, SelectCone, SelectFireWall, SelectFireball},
+        DefaultInputContext,
+    },
+    playing,
+};
+
+use super::{player::DRAGON_FRAME_SIZE, Player, HALF_TILE_SIZE, TILE_SIZE};
+
+/// Distance from the middle of the dragon to its mouth, where every breath comes out.
+pub const MOUTH_DISTANCE: f32 = DRAGON_FRAME_SIZE.x as f32 * 0.5;
+/// How far the cone reaches past the mouth once it has fully grown.
+pub const CONE_RANGE: f32 = TILE_SIZE.x * 10.;
+/// Angle between the middle of the cone and either of its sides, in radians.
+pub const CONE_HALF_ANGLE: f32 = 0.35;
+/// Half the width of the cone where it leaves the mouth.
+pub const CONE_MOUTH_HALF_WIDTH: f32 = 8.;
+
+pub(super) struct BreathAbilityPlugin;
+
+impl Plugin for BreathAbilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BreathEffectAssets>();
+
+        app.add_systems(FixedUpdate, tick_breath_cooldowns.run_if(playing()));
+
+        app.add_observer(select_cone);
+        app.add_observer(select_fireball);
+        app.add_observer(select_fire_wall);
+    }
+}
+
+/// What holding the breath button does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BreathAbility {
+    /// Continuous stream of fire ahead of the dragon.
+    #[default]
+    Cone,
+    /// Charged while held and launched on release, exploding on the first thing it hits.
+    Fireball,
+    /// Lays burning ground along the flight path for as long as it is held.
+    FireWall,
+}
+
+impl BreathAbility {
+    const COUNT: usize = 3;
+
+    /// Fire spent each tick by the cone, per shot by the fireball and per segment by the wall.
+    pub const fn cost(&self) -> i16 {
+        match self {
+            Self::Cone => 1,
+            Self::Fireball => 30,
+            Self::FireWall => 4,
+        }
+    }
+
+    /// Seconds before the ability can be used again once it was used.
+    pub const fn cooldown(&self) -> f32 {
+        match self {
+            Self::Cone => 0.,
+            Self::Fireball => 1.5,
+            Self::FireWall => 4.,
+        }
+    }
+
+    pub fn collider(&self) -> Collider {
+        match self {
+            Self::Cone => cone_collider(),
+            Self::Fireball => Collider::ball(12.),
+            Self::FireWall => Collider::cuboid(HALF_TILE_SIZE.x, HALF_TILE_SIZE.y),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Cone => "Cone",
+            Self::Fireball => "Fireball",
+            Self::FireWall => "Fire Wall",
+        }
+    }
+}
+
+/// Fully grown cone, pointing up from the mouth the way the dragon faces.
+fn cone_collider() -> Collider {
+    let far_half_width = CONE_MOUTH_HALF_WIDTH + CONE_RANGE * CONE_HALF_ANGLE.tan();
+
+    Collider::convex_hull(&[
+        Vec2::new(-CONE_MOUTH_HALF_WIDTH, 0.),
+        Vec2::new(CONE_MOUTH_HALF_WIDTH, 0.),
+        Vec2::new(far_half_width, CONE_RANGE),
+        Vec2::new(-far_half_width, CONE_RANGE),
+    ])
+    .expect("the breath cone is convex")
+}
+
+#[derive(Component, Default, Deref, PartialEq, Eq)]
+pub struct SelectedBreath(BreathAbility);
+
+#[derive(Component)]
+pub struct BreathCooldowns([Timer; BreathAbility::COUNT]);
+
+impl Default for BreathCooldowns {
+    fn default() -> Self {
+        let abilities = [
+            BreathAbility::Cone,
+            BreathAbility::Fireball,
+            BreathAbility::FireWall,
+        ];
+
+        Self(abilities.map(|ability| {
+            let mut timer = Timer::from_seconds(ability.cooldown(), TimerMode::Once);
+            // Every ability is ready at the start of a run.
+            timer.tick(timer.duration());
+            timer
+        }))
+    }
+}
+
+impl BreathCooldowns {
+    pub fn is_ready(&self, ability: BreathAbility) -> bool {
+        self.0[ability as usize].finished()
+    }
+
+    pub fn start(&mut self, ability: BreathAbility) {
+        self.0[ability as usize].reset();
+    }
+}
+
+/// Flames drawn by the breath abilities that leave something burning behind or blow up.
+#[derive(Resource)]
+pub struct BreathEffectAssets {
+    pub fire_image: Handle<Image>,
+    pub fire_layout: Handle<TextureAtlasLayout>,
+    pub fire_animation: Handle<SpriteAnimationSet>,
+    pub explosion_effect: Handle<Particle2dEffect>,
+}
+
+impl FromWorld for BreathEffectAssets {
+    fn from_world(world: &mut World) -> Self {
+        let fire_layout =
+            world
+                .resource_mut::<Assets<TextureAtlasLayout>>()
+                .add(TextureAtlasLayout::from_grid(
+                    UVec2::splat(40),
+                    2,
+                    1,
+                    None,
+                    None,
+                ));
+
+        let asset_server = world.resource::<AssetServer>();
+
+        Self {
+            fire_image: asset_server.load("textures/fire_anim.png"),
+            fire_layout,
+            fire_animation: asset_server.load("animations/fire.anim.ron"),
+            explosion_effect: asset_server.load("vfx/fireball_explosion.ron"),
+        }
+    }
+}
+
+impl BreathEffectAssets {
+    pub fn fire_sprite(&self) -> Sprite {
+        Sprite {
+            image: self.fire_image.clone(),
+            texture_atlas: Some(TextureAtlas::from(self.fire_layout.clone())),
+            ..default()
+        }
+    }
+}
+
+fn tick_breath_cooldowns(mut cooldowns: Single<&mut BreathCooldowns>, time: Res<Time>) {
+    for timer in &mut cooldowns.0 {
+        timer.tick(time.delta());
+    }
+}
+
+fn select_cone(
+    _trigger: Trigger<Started<SelectCone>>,
+    player: Single<(&Actions<DefaultInputContext>, &mut SelectedBreath), With<Player>>,
+) {
+    select_breath(player.into_inner(), BreathAbility::Cone);
+}
+
+fn select_fireball(
+    _trigger: Trigger<Started<SelectFireball>>,
+    player: Single<(&Actions<DefaultInputContext>, &mut SelectedBreath), With<Player>>,
+) {
+    select_breath(player.into_inner(), BreathAbility::Fireball);
+}
+
+fn select_fire_wall(
+    _trigger: Trigger<Started<SelectFireWall>>,
+    player: Single<(&Actions<DefaultInputContext>, &mut SelectedBreath), With<Player>>,
+) {
+    select_breath(player.into_inner(), BreathAbility::FireWall);
+}
+
+/// Switching is ignored while breathing so an ability is always finished the way it started.
+fn select_breath(
+    (actions, mut selected_breath): (&Actions<DefaultInputContext>, Mut<SelectedBreath>),
+    ability: BreathAbility,
+) {
+    if actions
+        .state::<FireBreath>()
+        .is_ok_and(|state| state == ActionState::None)
+    {
+        selected_breath.set_if_neq(SelectedBreath(ability));
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::{ecs::system::SystemParam, prelude::*, render::view::RenderLayers};
+use bevy_enhanced_input::prelude::*;
+use bevy_enoki::prelude::*;
+
+use crate::{
+    camera::{CameraShakeEvent, RenderLayer},
+    input::{actions::FireBreath, FIRE_BREATH_HOLD_SECONDS},
+    playing, AppState,
+};
+
+use super::{
+    altitude::Altitude,
+    breath_ability::{
+        BreathAbility, BreathEffectAssets, SelectedBreath, CONE_HALF_ANGLE, CONE_RANGE,
+        MOUTH_DISTANCE,
+    },
+    damage::{Damage, DamageEvent, DamageType},
+    heat::Heat,
+    resource_pool::{Fire, ResourcePool},
+    scorch::{ScorchEvent, ScorchKind},
+    spatial_index::{SpatialIndex, SpatialLayer},
+    Player, TILE_SIZE,
+};
+
+/// Charge below which letting go of the breath button releases nothing.
+pub const MIN_RELEASE_CHARGE: f32 = 0.25;
+const MIN_BURST_DAMAGE: f32 = 15.;
+const MAX_BURST_DAMAGE: f32 = 50.;
+/// Fire spent by a fully charged burst, and so the heat it builds.
+const MAX_BURST_COST: f32 = 20.;
+/// Reach of the burst past the mouth, as a share of the cone's full range.
+const MIN_BURST_REACH: f32 = 0.5;
+const MAX_BURST_REACH: f32 = 1.5;
+const MIN_BURST_SHAKE: f32 = 0.15;
+const MAX_BURST_SHAKE: f32 = 0.5;
+/// Explosions drawn along the burst, evenly spread over its reach.
+const BURST_FLAMES: usize = 4;
+/// Scorch marks left along the burst.
+const BURST_MARKS: usize = 6;
+
+pub(super) struct BreathChargePlugin;
+
+impl Plugin for BreathChargePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BreathBurst>();
+
+        app.add_systems(
+            FixedUpdate,
+            release_breath_bursts
+                .run_if(on_event::<BreathBurst>)
+                .run_if(playing()),
+        );
+
+        app.add_observer(charge_breath);
+        app.add_observer(hold_full_charge);
+        app.add_observer(clear_charge_on_cancel);
+        app.add_observer(clear_charge_on_completion);
+    }
+}
+
+/// Share of a full charge stored by holding the breath button for `elapsed_secs`.
+pub fn breath_charge(elapsed_secs: f32) -> f32 {
+    (elapsed_secs / FIRE_BREATH_HOLD_SECONDS).min(1.)
+}
+
+/// How far past the mouth a burst released with `charge` reaches.
+fn burst_reach(charge: f32) -> f32 {
+    CONE_RANGE * MIN_BURST_REACH.lerp(MAX_BURST_REACH, charge)
+}
+
+/// Heat stored by holding the breath button before the breath starts, shown on the HUD.
+#[derive(Component, Default, Deref)]
+pub struct BreathCharge(f32);
+
+/// Releases the stored heat of the cone as a single blast ahead of the dragon.
+#[derive(Event)]
+pub struct BreathBurst {
+    charge: f32,
+}
+
+impl BreathBurst {
+    pub fn new(charge: f32) -> Self {
+        Self { charge }
+    }
+}
+
+fn charge_breath(
+    trigger: Trigger<Ongoing<FireBreath>>,
+    mut charge: Single<&mut BreathCharge, With<Player>>,
+) {
+    charge.0 = breath_charge(trigger.elapsed_secs);
+}
+
+/// The cone and the wall spend their charge as soon as the breath starts, while the
+/// fireball keeps it until it is launched.
+fn hold_full_charge(
+    trigger: Trigger<Fired<FireBreath>>,
+    player: Single<(&mut BreathCharge, &SelectedBreath), With<Player>>,
+) {
+    if trigger.fired_secs != 0. {
+        return;
+    }
+
+    let (mut charge, selected_breath) = player.into_inner();
+    charge.0 = match **selected_breath {
+        BreathAbility::Fireball => 1.,
+        BreathAbility::Cone | BreathAbility::FireWall => 0.,
+    };
+}
+
+fn clear_charge_on_cancel(
+    _trigger: Trigger<Canceled<FireBreath>>,
+    mut charge: Single<&mut BreathCharge, With<Player>>,
+) {
+    charge.0 = 0.;
+}
+
+fn clear_charge_on_completion(
+    _trigger: Trigger<Completed<FireBreath>>,
+    mut charge: Single<&mut BreathCharge, With<Player>>,
+) {
+    charge.0 = 0.;
+}
+
+/// Hits everything inside a cone that reaches further and hurts more the longer the breath
+/// was charged. Costs fire in proportion to the charge, so bursts heat the dragon up too.
+/// Everything a burst shows once it fires: shaking, flames along its reach and the ground it scorches.
+#[derive(SystemParam)]
+struct BurstEffects<'w, 's> {
+    commands: Commands<'w, 's>,
+    camera_shake_event_writer: EventWriter<'w, CameraShakeEvent>,
+    scorch_event_writer: EventWriter<'w, ScorchEvent>,
+    breath_effect_assets: Res<'w, BreathEffectAssets>,
+}
+
+impl BurstEffects<'_, '_> {
+    fn play(&mut self, mouth: Vec2, direction: Vec2, reach: f32, charge: f32) {
+        self.camera_shake_event_writer.write(CameraShakeEvent::new(
+            MIN_BURST_SHAKE.lerp(MAX_BURST_SHAKE, charge),
+        ));
+
+        for flame in 1..=BURST_FLAMES {
+            let distance = reach * flame as f32 / BURST_FLAMES as f32;
+
+            self.commands.spawn((
+                ParticleSpawner::default(),
+                ParticleEffectHandle(self.breath_effect_assets.explosion_effect.clone()),
+                OneShot::Despawn,
+                RenderLayers::layer(RenderLayer::Ground.into()),
+                Transform::from_translation((mouth + direction * distance).extend(10.))
+                    .with_scale(Vec3::splat(0.5 + charge)),
+                StateScoped(AppState::GameOver),
+            ));
+        }
+
+        for mark in 1..=BURST_MARKS {
+            let distance = reach * mark as f32 / BURST_MARKS as f32;
+
+            self.scorch_event_writer.write(ScorchEvent::new(
+                mouth + direction * distance,
+                TILE_SIZE.x * (0.5 + charge),
+                ScorchKind::Scorch,
+            ));
+        }
+    }
+}
+
+fn release_breath_bursts(
+    mut burst_event_reader: EventReader<BreathBurst>,
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    player: Single<
+        (
+            Entity,
+            &Transform,
+            &mut ResourcePool<Fire>,
+            &Heat,
+            &Altitude,
+        ),
+        With<Player>,
+    >,
+    spatial_index: Res<SpatialIndex>,
+    mut burst_effects: BurstEffects,
+) {
+    let (player_entity, player_transform, mut fire_resource_pool, heat, altitude) =
+        player.into_inner();
+
+    if fire_resource_pool.is_empty() || heat.is_overheated() {
+        burst_event_reader.clear();
+        return;
+    }
+
+    let direction = player_transform.rotation.mul_vec3(Vec3::Y).xy();
+    let mouth = player_transform.translation.xy() + direction * MOUTH_DISTANCE;
+
+    for &BreathBurst { charge } in burst_event_reader.read() {
+        let cost = (MAX_BURST_COST * charge).ceil() as i16;
+
+        if fire_resource_pool.current() < cost {
+            continue;
+        }
+
+        fire_resource_pool.subtract(cost);
+
+        let reach = burst_reach(charge);
+        let damage = Damage::new(
+            (MIN_BURST_DAMAGE.lerp(MAX_BURST_DAMAGE, charge) * altitude.breath_effectiveness())
+                as i16,
+            DamageType::Fire,
+        );
+
+        for layer in [SpatialLayer::Enemies, SpatialLayer::Buildings] {
+            for (target, position) in spatial_index.query_radius(layer, mouth, reach) {
+                if direction.angle_to(position - mouth).abs() <= CONE_HALF_ANGLE {
+                    damage_event_writer.write(DamageEvent::new(
+                        player_entity,
+                        target,
+                        damage,
+                        mouth,
+                    ));
+                }
+            }
+        }
+
+        burst_effects.play(mouth, direction, reach, charge);
+    }
+}
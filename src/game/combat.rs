@@ -1,19 +1,36 @@
+use std::collections::{HashMap, HashSet};
+
 use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_enoki::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
+    animation::SpriteAnimation,
     camera::{RenderLayer, YSorted},
     game::{game_timer::GameTimer, level::Building},
     playing, AppState,
 };
 
 use super::{
-    power_up::{PowerUpEvent, PowerUpEventType},
+    altitude::Altitude,
+    damage::{Damage, DamageDealt, DamageEvent, DamageType},
+    death::{DeathCause, DeathEvent, Despawn, Dying},
+    entity_pool::{EntityPool, Pooled},
+    fire_contagion::FireSpread,
     resource_pool::{Fire, Health, ResourcePool},
     score_system::{ScoreEvent, ScoreEventType},
-    Enemy, Player, PLAYER_GROUP, PROJECTILE_GROUP, TILE_SIZE,
+    siege::SiegeEngine,
+    spatial_index::{SpatialIndex, SpatialLayer},
+    status_effect::{ApplyStatusEffect, StatusEffectExpired, BURNING, NETTED, POISONED, STUNNED},
+    Enemy, Player, FIRE_BREATH_GROUP, HIGH_PROJECTILE_GROUP, PLAYER_GROUP, PROJECTILE_GROUP,
+    TILE_SIZE,
 };
 
+/// Share of its maximum health a burning unit is left with after its fire is put out.
+pub const EXTINGUISHED_HEALTH_RATIO: f32 = 0.25;
+/// Damage a building caught in a fire takes each second until the fire dies down.
+const BURNING_BUILDING_DAMAGE_PER_SECOND: f32 = 40.;
+
 pub(super) struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
@@ -25,37 +42,171 @@ impl Plugin for CombatPlugin {
             (
                 projectile_collision_with_player,
                 spawn_projectiles,
-                despawn_projectiles,
-                despawn_dead_entities,
-                despawn_on_fire_entities,
+                update_arcing_projectiles,
+                steer_homing_projectiles,
+                expire_projectiles,
+                handle_slain_entities,
+                burn_out_entities,
+                burn_buildings,
                 compute_damage_from_intersections,
+                incinerate_projectiles,
+                reset_multiplier_on_player_damage,
             )
                 .run_if(playing()),
         );
+
+        app.add_observer(forget_pooled_emitters);
+        app.add_observer(remove_landing_markers);
     }
 }
 
 #[derive(Event)]
 pub struct SpawnProjectileEvent {
-    damage: i16,
-    direction: Vec2,
+    damage: Damage,
     emitter: Entity,
+    kind: ProjectileKind,
     position: Vec2,
-    speed: f32,
+    /// Where the shot is aimed, and where lobbed shots come down.
+    target: Vec2,
 }
 
 impl SpawnProjectileEvent {
-    pub fn new(damage: i16, direction: Vec2, emitter: Entity, position: Vec2, speed: f32) -> Self {
+    pub fn new(damage: Damage, emitter: Entity, position: Vec2, target: Vec2) -> Self {
         Self {
             damage,
-            direction,
             emitter,
+            kind: ProjectileKind::Arrow,
             position,
-            speed,
+            target,
+        }
+    }
+
+    pub fn with_kind(mut self, kind: ProjectileKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectileKind {
+    /// Light arrow that curves slightly towards the player.
+    Arrow,
+    /// Fast crossbow bolt that punches through its first target.
+    Quarrel,
+    /// Spinning axe thrown in an arc.
+    ThrowingAxe,
+    /// Heavy ballista bolt that keeps flying after hitting its targets.
+    Bolt,
+    /// Lobbed over everything in its path, crushing whatever is near where it lands.
+    Boulder,
+}
+
+impl ProjectileKind {
+    pub fn definition(&self) -> ProjectileDefinition {
+        match self {
+            Self::Arrow => ProjectileDefinition {
+                homing: 0.6,
+                ..default()
+            },
+            Self::Quarrel => ProjectileDefinition {
+                color: Color::srgb(0.25, 0.15, 0.05),
+                size: Vec2::new(TILE_SIZE.x * 0.75, 3.),
+                speed: 1100.,
+                lifetime: 1.2,
+                max_distance: TILE_SIZE.x * 40.,
+                pierce: 1,
+                status_effect: Some(POISONED),
+                ..default()
+            },
+            Self::ThrowingAxe => ProjectileDefinition {
+                color: Color::srgb(0.55, 0.5, 0.45),
+                size: Vec2::splat(TILE_SIZE.x * 0.5),
+                speed: 400.,
+                arc: Some(0.25),
+                impact_radius: TILE_SIZE.x,
+                spin: 12.,
+                combustible: false,
+                ..default()
+            },
+            Self::Bolt => ProjectileDefinition {
+                color: Color::srgb(0.3, 0.2, 0.1),
+                size: Vec2::new(TILE_SIZE.x * 2.5, 6.),
+                speed: 1200.,
+                lifetime: 2.,
+                max_distance: TILE_SIZE.x * 60.,
+                pierce: 3,
+                trail: Some("vfx/bolt_trail.ron"),
+                status_effect: Some(NETTED),
+                reaches_high_altitude: true,
+                ..default()
+            },
+            Self::Boulder => ProjectileDefinition {
+                color: Color::srgb(0.4, 0.4, 0.4),
+                size: Vec2::splat(TILE_SIZE.x * 0.75),
+                speed: 250.,
+                arc: Some(0.3),
+                impact_radius: TILE_SIZE.x * 3.,
+                combustible: false,
+                status_effect: Some(STUNNED),
+                ..default()
+            },
+        }
+    }
+}
+
+/// How a kind of projectile looks and flies. Defaults to a plain arrow.
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectileDefinition {
+    pub color: Color,
+    pub size: Vec2,
+    pub speed: f32,
+    /// Seconds before a straight shot drops out of the air.
+    pub lifetime: f32,
+    /// Distance from where it was fired at which a straight shot drops out of the air.
+    pub max_distance: f32,
+    /// Apex height of a lobbed shot relative to the distance it covers. Lobbed shots fly over
+    /// everything in their path and only hit what is within `impact_radius` of where they land.
+    pub arc: Option<f32>,
+    pub impact_radius: f32,
+    /// How fast a straight shot turns towards the player, in radians per second.
+    pub homing: f32,
+    /// Number of targets a straight shot passes through before it stops.
+    pub pierce: u32,
+    /// Radians per second a lobbed shot turns while in the air.
+    pub spin: f32,
+    /// Particle effect trailing behind the projectile.
+    pub trail: Option<&'static str>,
+    /// Wooden straight shots burn up in the breath, stone and metal ones fly through it.
+    pub combustible: bool,
+    /// Status effect applied to whatever the projectile hits.
+    pub status_effect: Option<&'static str>,
+    /// Straight shots heavy enough to hit the dragon while it flies high.
+    pub reaches_high_altitude: bool,
+}
+
+impl Default for ProjectileDefinition {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            size: Vec2::new(TILE_SIZE.x, 4.),
+            speed: 800.,
+            lifetime: 1.5,
+            max_distance: TILE_SIZE.x * 30.,
+            arc: None,
+            impact_radius: 0.,
+            homing: 0.,
+            pierce: 0,
+            spin: 0.,
+            trail: None,
+            combustible: true,
+            status_effect: None,
+            reaches_high_altitude: false,
         }
     }
 }
 
+/// Entity credited with the projectile's hits. The projectile itself once whoever fired it
+/// was handed back to a pool, so a unit reusing that entity isn't credited instead.
 #[derive(Component)]
 pub struct Emitter(Entity);
 
@@ -64,11 +215,32 @@ pub struct Range(pub f32);
 
 /// Represents the damage this entity causes to others when colliding.
 #[derive(Component)]
-pub struct ImpactDamage(pub i16);
+pub struct ImpactDamage(pub Damage);
+
+/// Damage dealt over time to whatever the entity overlaps. Overlapping sources don't add up,
+/// a target only takes damage from the strongest one of each type.
+#[derive(Component, Clone, Copy)]
+pub struct DamagePerSecond {
+    pub amount: f32,
+    pub damage_type: DamageType,
+}
+
+impl DamagePerSecond {
+    pub fn new(amount: f32, damage_type: DamageType) -> Self {
+        Self {
+            amount,
+            damage_type,
+        }
+    }
+}
 
 /// Represents an Entity's damage attributes.
 #[derive(Component)]
-pub struct AttackDamage(pub i16);
+pub struct AttackDamage(pub Damage);
+
+/// Kind of projectile an entity attacks with.
+#[derive(Component)]
+pub struct AttackProjectile(pub ProjectileKind);
 
 #[derive(Component, Deref, DerefMut)]
 pub struct AttackTimer(Timer);
@@ -79,173 +251,640 @@ impl AttackTimer {
     }
 }
 
+/// How long it burns and how it affects the entity comes from the [`BURNING`] status effect.
 #[derive(Component)]
-#[require(GameTimer::<OnFire>::from_seconds_once(3.0))]
+#[require(FireSpread)]
 pub struct OnFire;
 
 #[derive(Component)]
 #[require(
     Ccd::enabled(),
-    Collider::cuboid(Projectile::DEFAULT_SIZE.x / 2., Projectile::DEFAULT_SIZE.y / 2.),
     CollisionGroups::new(PROJECTILE_GROUP, PLAYER_GROUP | PROJECTILE_GROUP),
     RenderLayers::layer(RenderLayer::Sky.into()),
     RigidBody::Dynamic,
     Damping {
-        linear_damping: 1.0,
+        linear_damping: 0.,
         angular_damping: 10.0,
     },
     StateScoped::<AppState>(AppState::GameOver),
 )]
 pub struct Projectile;
 
-impl Projectile {
-    const DEFAULT_SIZE: Vec2 = Vec2::new(TILE_SIZE.x, 4.);
+/// Point a straight shot was fired from and how far it can get from it.
+#[derive(Component)]
+pub struct TravelLimit {
+    origin: Vec2,
+    max_distance: f32,
+}
+
+/// Status effect a projectile applies to whatever it hits.
+#[derive(Component)]
+pub struct InflictsStatus(&'static str);
+
+/// Burns up when the breath touches it.
+#[derive(Component)]
+pub struct Combustible;
+
+/// Turns a projectile towards the player, in radians per second.
+#[derive(Component)]
+pub struct Homing(f32);
+
+/// Keeps track of what a piercing projectile already damaged so it only hits each target once.
+#[derive(Component)]
+pub struct Piercing {
+    /// Targets it can still pass through before stopping at the next one.
+    remaining: u32,
+    hit: Vec<Entity>,
+}
+
+/// Moves a projectile along a parabola between two points, ignoring collisions until it lands.
+#[derive(Component)]
+pub struct ArcingProjectile {
+    start: Vec2,
+    target: Vec2,
+    apex_height: f32,
+    spin: f32,
+    flight_timer: Timer,
+}
+
+/// Damages the player on landing if they are within the radius of the impact point.
+#[derive(Component)]
+pub struct AreaDamage {
+    radius: f32,
+    marker: Entity,
+}
+
+/// Lobbed shots can also be handed back to their pool before they land.
+fn remove_landing_markers(
+    trigger: Trigger<OnRemove, AreaDamage>,
+    mut commands: Commands,
+    query: Query<&AreaDamage>,
+) {
+    if let Ok(area_damage) = query.get(trigger.target()) {
+        commands.entity(area_damage.marker).try_despawn();
+    }
 }
 
 fn spawn_projectiles(
     mut commands: Commands,
     mut spawn_projectile_event_reader: EventReader<SpawnProjectileEvent>,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    asset_server: Res<AssetServer>,
 ) {
     for &SpawnProjectileEvent {
         damage,
-        direction,
         emitter,
+        kind,
         position,
-        speed,
+        target,
     } in spawn_projectile_event_reader.read()
     {
-        let angle = if direction != Vec2::ZERO {
-            let mut angle = direction.angle_to(Vec2::X);
-            if !angle.is_finite() {
-                angle = 0.;
+        let definition = kind.definition();
+        let projectile = projectile_pool
+            .acquire(&mut commands)
+            .insert((
+                ImpactDamage(damage),
+                Emitter(emitter),
+                Projectile,
+                Collider::cuboid(definition.size.x / 2., definition.size.y / 2.),
+                Sprite {
+                    color: definition.color,
+                    custom_size: Some(definition.size),
+                    ..default()
+                },
+            ))
+            .id();
+
+        match definition.arc {
+            Some(apex_ratio) => {
+                let distance = position.distance(target);
+                let flight_seconds = (distance / definition.speed).max(0.5);
+                let marker = commands
+                    .spawn((
+                        Mesh2d(meshes.add(Circle::new(definition.impact_radius))),
+                        MeshMaterial2d(materials.add(Color::srgba(0.8, 0.1, 0.1, 0.35))),
+                        Transform::from_translation(target.extend(0.5)),
+                        RenderLayers::layer(RenderLayer::Ground.into()),
+                        StateScoped(AppState::GameOver),
+                    ))
+                    .id();
+
+                commands.entity(projectile).insert((
+                    ArcingProjectile {
+                        start: position,
+                        target,
+                        apex_height: distance * apex_ratio,
+                        spin: definition.spin,
+                        flight_timer: Timer::from_seconds(flight_seconds, TimerMode::Once),
+                    },
+                    AreaDamage {
+                        radius: definition.impact_radius,
+                        marker,
+                    },
+                    RigidBody::KinematicPositionBased,
+                    CollisionGroups::new(Group::NONE, Group::NONE),
+                    Transform::from_translation(position.extend(1.0)),
+                ));
             }
-            angle
-        } else {
-            0.
-        };
+            None => {
+                let direction = (target - position).normalize_or_zero();
+                let mut projectile = commands.entity(projectile);
 
-        commands.spawn((
-            ImpactDamage(damage),
-            Emitter(emitter),
-            Projectile,
-            Sprite {
-                color: Color::BLACK,
-                custom_size: Some(Projectile::DEFAULT_SIZE),
-                ..default()
-            },
-            Transform::from_translation(position.extend(1.0))
-                .with_rotation(Quat::from_rotation_z(-angle)),
-            Velocity {
-                linvel: direction * speed,
-                angvel: 0.,
-            },
-            YSorted,
-        ));
+                projectile.insert((
+                    GameTimer::<Projectile>::from_seconds_once(definition.lifetime),
+                    TravelLimit {
+                        origin: position,
+                        max_distance: definition.max_distance,
+                    },
+                    Transform::from_translation(position.extend(1.0))
+                        .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+                    Velocity::linear(direction * definition.speed),
+                    YSorted,
+                ));
+
+                if definition.homing > 0. {
+                    projectile.insert(Homing(definition.homing));
+                }
+
+                let mut memberships = PROJECTILE_GROUP;
+                let mut filters = PLAYER_GROUP | PROJECTILE_GROUP;
+
+                if definition.reaches_high_altitude {
+                    memberships |= HIGH_PROJECTILE_GROUP;
+                }
+
+                if definition.combustible {
+                    projectile.insert(Combustible);
+                    filters |= FIRE_BREATH_GROUP;
+                }
+
+                projectile.insert(CollisionGroups::new(memberships, filters));
+
+                // Piercing shots are sensors so they fly on through whatever they hit.
+                if definition.pierce > 0 {
+                    projectile.insert((
+                        Piercing {
+                            remaining: definition.pierce,
+                            hit: Vec::new(),
+                        },
+                        Sensor,
+                    ));
+                }
+            }
+        }
+
+        if let Some(status_effect) = definition.status_effect {
+            commands
+                .entity(projectile)
+                .insert(InflictsStatus(status_effect));
+        }
+
+        if let Some(trail) = definition.trail {
+            commands.entity(projectile).with_child((
+                ParticleSpawner::default(),
+                ParticleEffectHandle(asset_server.load(trail)),
+                RenderLayers::layer(RenderLayer::Sky.into()),
+            ));
+        }
+    }
+}
+
+fn update_arcing_projectiles(
+    mut commands: Commands,
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffect>,
+    mut projectile_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut ArcingProjectile,
+            &ImpactDamage,
+            &Emitter,
+            Option<&AreaDamage>,
+            Option<&InflictsStatus>,
+        ),
+        With<Projectile>,
+    >,
+    player: Single<(Entity, &Transform, &Altitude), (With<Player>, Without<Projectile>)>,
+    spatial_index: Res<SpatialIndex>,
+    time: Res<Time>,
+) {
+    let (player_entity, player_transform, altitude) = player.into_inner();
+
+    for (
+        entity,
+        mut transform,
+        mut arcing_projectile,
+        impact_damage,
+        emitter,
+        area_damage,
+        inflicts_status,
+    ) in &mut projectile_query
+    {
+        let progress = arcing_projectile.flight_timer.tick(time.delta()).fraction();
+        let ground_position = arcing_projectile
+            .start
+            .lerp(arcing_projectile.target, progress);
+        let height = 4. * arcing_projectile.apex_height * progress * (1. - progress);
+
+        transform.translation.x = ground_position.x;
+        transform.translation.y = ground_position.y + height;
+        transform.scale = Vec3::splat(1. + height / arcing_projectile.apex_height.max(1.));
+        transform.rotate_z(arcing_projectile.spin * time.delta_secs());
+
+        if !arcing_projectile.flight_timer.finished() {
+            continue;
+        }
+
+        if let Some(area_damage) = area_damage {
+            let player_position = player_transform.translation.xy();
+
+            // Lobbed shots land on the ground, far below a dragon flying high.
+            if !altitude.is_high()
+                && player_position.distance(arcing_projectile.target) <= area_damage.radius
+            {
+                damage_event_writer.write(DamageEvent::new(
+                    emitter.0,
+                    player_entity,
+                    impact_damage.0,
+                    arcing_projectile.target,
+                ));
+
+                if let Some(inflicts_status) = inflicts_status {
+                    apply_status_effect_event_writer.write(ApplyStatusEffect::new(
+                        player_entity,
+                        inflicts_status.0,
+                        Some(emitter.0),
+                    ));
+                }
+            }
+
+            // Units caught under a lobbed shot only suffer its status effect, not its damage.
+            if let Some(inflicts_status) = inflicts_status {
+                for (enemy, _) in spatial_index.query_radius(
+                    SpatialLayer::Enemies,
+                    arcing_projectile.target,
+                    area_damage.radius,
+                ) {
+                    apply_status_effect_event_writer.write(ApplyStatusEffect::new(
+                        enemy,
+                        inflicts_status.0,
+                        Some(emitter.0),
+                    ));
+                }
+            }
+        }
+
+        commands.entity(entity).insert(Despawn);
     }
 }
 
 fn projectile_collision_with_player(
     mut commands: Commands,
-    mut score_event_writer: EventWriter<ScoreEvent>,
-    player: Single<(Entity, &mut ResourcePool<Health>), With<Player>>,
-    projectile_query: Query<(Entity, &ImpactDamage), With<Projectile>>,
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffect>,
+    player_entity: Single<Entity, With<Player>>,
+    mut projectile_query: Query<
+        (
+            Entity,
+            &Transform,
+            &ImpactDamage,
+            &Emitter,
+            Option<&mut Piercing>,
+            Option<&InflictsStatus>,
+        ),
+        With<Projectile>,
+    >,
     rapier_context: ReadRapierContext,
 ) {
-    let (player_entity, mut player_hitpoints) = player.into_inner();
+    let player_entity = *player_entity;
     let Ok(rapier_context) = rapier_context.single() else {
         return;
     };
 
-    for (projectile_entity, projectile_damage) in &projectile_query {
-        if let Some(contact_pair) = rapier_context.contact_pair(player_entity, projectile_entity) {
-            if contact_pair.has_any_active_contact() {
-                player_hitpoints.subtract(projectile_damage.0);
-                score_event_writer.write(ScoreEvent::new(0, ScoreEventType::ResetMultiplier));
+    for (
+        projectile_entity,
+        projectile_transform,
+        projectile_damage,
+        emitter,
+        piercing,
+        inflicts_status,
+    ) in &mut projectile_query
+    {
+        let damage_event = DamageEvent::new(
+            emitter.0,
+            player_entity,
+            projectile_damage.0,
+            projectile_transform.translation.xy(),
+        );
+        let status_event = inflicts_status.map(|inflicts_status| {
+            ApplyStatusEffect::new(player_entity, inflicts_status.0, Some(emitter.0))
+        });
+
+        match piercing {
+            // Piercing projectiles are sensors, so they overlap the player instead of touching it.
+            Some(mut piercing) => {
+                if !piercing.hit.contains(&player_entity)
+                    && rapier_context.intersection_pair(player_entity, projectile_entity)
+                        == Some(true)
+                {
+                    damage_event_writer.write(damage_event);
+                    apply_status_effect_event_writer.write_batch(status_event);
+                    piercing.hit.push(player_entity);
 
-                // TODO: Add "death" component or event and use it here so a different system handles despawns.
-                commands.entity(projectile_entity).despawn();
+                    match piercing.remaining.checked_sub(1) {
+                        Some(remaining) => piercing.remaining = remaining,
+                        None => {
+                            commands.entity(projectile_entity).insert(Despawn);
+                        }
+                    }
+                }
+            }
+            None => {
+                if let Some(contact_pair) =
+                    rapier_context.contact_pair(player_entity, projectile_entity)
+                {
+                    if contact_pair.has_any_active_contact() {
+                        damage_event_writer.write(damage_event);
+                        apply_status_effect_event_writer.write_batch(status_event);
+                        commands.entity(projectile_entity).insert(Despawn);
+                    }
+                }
             }
         }
     }
 }
 
-fn compute_damage_from_intersections(
-    mut enemy_query: Query<&mut ResourcePool<Health>, With<Enemy>>,
-    fire_query: Query<(Entity, &ImpactDamage), With<Fire>>,
+/// Damage below a whole hitpoint is carried over to the next tick for as long as the target
+/// stays in the breath, so the damage dealt doesn't depend on the timestep.
+pub(super) fn compute_damage_from_intersections(
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut accumulated_damage: Local<HashMap<(Entity, DamageType), f32>>,
+    enemy_query: Query<(), With<Enemy>>,
+    fire_query: Query<(Entity, &GlobalTransform, &DamagePerSecond), With<Fire>>,
+    player_entity: Single<Entity, With<Player>>,
     rapier_context: ReadRapierContext,
+    time: Res<Time>,
 ) {
     let Ok(rapier_context) = rapier_context.single() else {
         return;
     };
 
-    for (entity, damage) in &fire_query {
+    // Strongest source of each type touching each target, and where it is.
+    let mut exposure = HashMap::<(Entity, DamageType), (f32, Vec2)>::new();
+
+    for (entity, fire_transform, damage_per_second) in &fire_query {
         for (entity1, entity2, intersecting) in rapier_context.intersection_pairs_with(entity) {
             let other_entity = if entity1 == entity { entity2 } else { entity1 };
 
-            if intersecting {
-                if let Ok(mut enemy_hitpoints) = enemy_query.get_mut(other_entity) {
-                    enemy_hitpoints.subtract(damage.0);
+            if intersecting && enemy_query.contains(other_entity) {
+                let (amount, position) = exposure
+                    .entry((other_entity, damage_per_second.damage_type))
+                    .or_default();
+
+                if damage_per_second.amount > *amount {
+                    *amount = damage_per_second.amount;
+                    *position = fire_transform.translation().xy();
                 }
             }
         }
     }
+
+    // Targets that left the breath lose whatever fraction they had built up.
+    let previous_damage = std::mem::take(&mut *accumulated_damage);
+
+    for ((target, damage_type), (damage_per_second, source_position)) in exposure {
+        let accumulated = previous_damage
+            .get(&(target, damage_type))
+            .copied()
+            .unwrap_or_default()
+            + damage_per_second * time.delta_secs();
+
+        if accumulated >= 1. {
+            // Hits from the side of the fire itself, so burning ground laid behind a unit
+            // gets around its shield.
+            damage_event_writer.write(DamageEvent::new(
+                *player_entity,
+                target,
+                Damage::new(accumulated.trunc() as i16, damage_type),
+                source_position,
+            ));
+        }
+
+        accumulated_damage.insert((target, damage_type), accumulated.fract());
+    }
+}
+
+fn forget_pooled_emitters(
+    trigger: Trigger<OnAdd, Pooled>,
+    mut query: Query<(Entity, &mut Emitter)>,
+) {
+    for (projectile, mut emitter) in &mut query {
+        if emitter.0 == trigger.target() {
+            emitter.0 = projectile;
+        }
+    }
 }
 
-fn despawn_dead_entities(
+/// Breathing on wooden projectiles burns them up before they reach the dragon.
+pub(super) fn incinerate_projectiles(
     mut commands: Commands,
     mut score_event_writer: EventWriter<ScoreEvent>,
-    mut powerup_event_writer: EventWriter<PowerUpEvent>,
+    projectile_query: Query<&Transform, (With<Projectile>, With<Combustible>)>,
+    fire_query: Query<Entity, With<Fire>>,
+    rapier_context: ReadRapierContext,
+    asset_server: Res<AssetServer>,
+    mut incinerated: Local<HashSet<Entity>>,
+) {
+    const INCINERATION_POINTS: i32 = 2;
+
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+    incinerated.clear();
+
+    for entity in &fire_query {
+        for (entity1, entity2, intersecting) in rapier_context.intersection_pairs_with(entity) {
+            let other_entity = if entity1 == entity { entity2 } else { entity1 };
+
+            if !intersecting || incinerated.contains(&other_entity) {
+                continue;
+            }
+
+            let Ok(projectile_transform) = projectile_query.get(other_entity) else {
+                continue;
+            };
+
+            commands.entity(other_entity).insert(Despawn);
+            commands.spawn((
+                ParticleSpawner::default(),
+                ParticleEffectHandle(asset_server.load("vfx/burn_up.ron")),
+                OneShot::Despawn,
+                RenderLayers::layer(RenderLayer::Sky.into()),
+                Transform::from_translation(projectile_transform.translation.xy().extend(10.0)),
+                StateScoped(AppState::GameOver),
+            ));
+            score_event_writer.write(ScoreEvent::new(
+                INCINERATION_POINTS,
+                ScoreEventType::AddPoints,
+            ));
+            incinerated.insert(other_entity);
+        }
+    }
+}
+
+/// Getting hurt costs the player their score multiplier.
+fn reset_multiplier_on_player_damage(
+    mut damage_dealt_event_reader: EventReader<DamageDealt>,
+    mut score_event_writer: EventWriter<ScoreEvent>,
+    player_entity: Single<Entity, With<Player>>,
+) {
+    if damage_dealt_event_reader
+        .read()
+        .any(|damage_dealt| damage_dealt.target == *player_entity)
+    {
+        score_event_writer.write(ScoreEvent::new(0, ScoreEventType::ResetMultiplier));
+    }
+}
+
+/// Slain units are set alight and burn out on their own, anything else comes down at once.
+fn handle_slain_entities(
+    mut commands: Commands,
+    mut death_event_reader: EventReader<DeathEvent>,
+    query: Query<(Has<Building>, Has<SiegeEngine>, Has<OnFire>), With<Enemy>>,
+) {
+    for death_event in death_event_reader.read() {
+        if death_event.cause != DeathCause::Slain {
+            continue;
+        }
+
+        let Ok((building, siege_engine, on_fire)) = query.get(death_event.entity) else {
+            continue;
+        };
+
+        if building || siege_engine {
+            commands.entity(death_event.entity).try_insert(Despawn);
+        } else if !on_fire {
+            commands.entity(death_event.entity).try_insert(OnFire);
+        }
+    }
+}
+
+fn burn_out_entities(
+    mut commands: Commands,
+    mut status_effect_expired_event_reader: EventReader<StatusEffectExpired>,
+    mut death_event_writer: EventWriter<DeathEvent>,
     query: Query<
         (
-            Entity,
-            &ResourcePool<Health>,
             &Transform,
+            &ResourcePool<Health>,
+            Has<SpriteAnimation>,
             Has<Building>,
-            Has<Enemy>,
-        ),
-        (
-            Without<Player>,
-            Without<OnFire>,
-            Changed<ResourcePool<Health>>,
         ),
+        With<OnFire>,
     >,
 ) {
-    for (entity, health, transform, building, enemy) in &query {
-        if health.current() == 0 {
-            if !building && enemy {
-                commands.entity(entity).insert(OnFire);
-            } else {
-                commands.entity(entity).despawn();
-            }
-            score_event_writer.write(ScoreEvent::new(10, ScoreEventType::AddPoints));
-            powerup_event_writer.write(PowerUpEvent::new(
-                *transform,
-                PowerUpEventType::HealingScale,
+    for &StatusEffectExpired { entity, effect } in status_effect_expired_event_reader.read() {
+        if effect != BURNING {
+            continue;
+        }
+
+        let Ok((transform, health, animated, building)) = query.get(entity) else {
+            continue;
+        };
+
+        // Buildings only come down once the fire ate through their health.
+        if building {
+            commands.entity(entity).try_remove::<OnFire>();
+            continue;
+        }
+
+        // Units whose health ran out already died when they were set alight.
+        if !health.is_empty() {
+            death_event_writer.write(DeathEvent::new(
+                entity,
+                DeathCause::BurnedOut,
+                None,
+                transform.translation.xy(),
             ));
         }
+
+        if animated {
+            commands.entity(entity).try_insert(Dying);
+        } else {
+            commands.entity(entity).try_insert(Despawn);
+        }
     }
 }
 
-fn despawn_on_fire_entities(
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut GameTimer<OnFire>), With<OnFire>>,
+/// Damage below a whole hitpoint is carried over to the next tick for as long as the building
+/// keeps burning.
+fn burn_buildings(
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut accumulated_damage: Local<HashMap<Entity, f32>>,
+    query: Query<(Entity, &Transform), (With<Building>, With<OnFire>)>,
     time: Res<Time>,
 ) {
-    for (entity, mut timer) in &mut query {
-        if timer.tick(time.delta()).just_finished() {
-            commands.entity(entity).despawn();
+    let previous_damage = std::mem::take(&mut *accumulated_damage);
+
+    for (entity, transform) in &query {
+        let accumulated = previous_damage.get(&entity).copied().unwrap_or_default()
+            + BURNING_BUILDING_DAMAGE_PER_SECOND * time.delta_secs();
+
+        if accumulated >= 1. {
+            damage_event_writer.write(DamageEvent::new(
+                entity,
+                entity,
+                Damage::new(accumulated.trunc() as i16, DamageType::Fire),
+                transform.translation.xy(),
+            ));
         }
+
+        accumulated_damage.insert(entity, accumulated.fract());
     }
 }
 
-fn despawn_projectiles(
+fn steer_homing_projectiles(
+    mut projectile_query: Query<(&mut Transform, &mut Velocity, &Homing), With<Projectile>>,
+    player_transform: Single<&Transform, (With<Player>, Without<Projectile>)>,
+    time: Res<Time>,
+) {
+    let player_position = player_transform.translation.xy();
+
+    for (mut transform, mut velocity, homing) in &mut projectile_query {
+        let to_player = player_position - transform.translation.xy();
+
+        if velocity.linvel == Vec2::ZERO || to_player == Vec2::ZERO {
+            continue;
+        }
+
+        let max_turn = homing.0 * time.delta_secs();
+        let turn = velocity
+            .linvel
+            .angle_to(to_player)
+            .clamp(-max_turn, max_turn);
+
+        velocity.linvel = Vec2::from_angle(turn).rotate(velocity.linvel);
+        transform.rotation = Quat::from_rotation_z(velocity.linvel.to_angle());
+    }
+}
+
+/// Straight shots drop out of the air once they flew for too long or too far.
+fn expire_projectiles(
     mut commands: Commands,
-    projectile_query: Query<(Entity, &Velocity), (With<Projectile>, Changed<Velocity>)>,
+    mut projectile_query: Query<
+        (Entity, &Transform, &TravelLimit, &mut GameTimer<Projectile>),
+        With<Projectile>,
+    >,
+    time: Res<Time>,
 ) {
-    for (entity, velocity) in &projectile_query {
-        if velocity.linvel.length() < 60. {
-            // TODO: Decouple this with a Despawn component
-            commands.entity(entity).despawn();
+    for (entity, transform, travel_limit, mut lifetime) in &mut projectile_query {
+        let travelled = transform.translation.xy().distance(travel_limit.origin);
+
+        if lifetime.tick(time.delta()).finished() || travelled > travel_limit.max_distance {
+            commands.entity(entity).insert(Despawn);
         }
     }
 }
@@ -12,3 +12,6 @@ pub const PROJECTILE_GROUP: Group = Group::GROUP_3;
 pub const BUILDING_GROUP: Group = Group::GROUP_4;
 pub const FIRE_BREATH_GROUP: Group = Group::GROUP_5;
 pub const POWERUP_GROUP: Group = Group::GROUP_6;
+pub const BARRIER_GROUP: Group = Group::GROUP_7;
+/// Projectiles that still reach the dragon when it flies high.
+pub const HIGH_PROJECTILE_GROUP: Group = Group::GROUP_8;
@@ -0,0 +1,248 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::playing;
+
+use super::{
+    death::{DeathCause, DeathEvent, Dying},
+    mage::WaterBarriers,
+    resource_pool::{Health, ResourcePool},
+    shield::Shield,
+    status_effect::StatusModifiers,
+    Player,
+};
+
+pub(super) struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>();
+        app.add_event::<DamageDealt>();
+
+        app.add_systems(FixedUpdate, tick_invulnerability.run_if(playing()));
+        // Resolved after `FixedUpdate` so damage from every source in the tick goes through it.
+        app.add_systems(FixedPostUpdate, resolve_damage.run_if(playing()));
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    Fire,
+    Piercing,
+    Blunt,
+    Magic,
+}
+
+impl DamageType {
+    pub const COUNT: usize = 4;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Damage {
+    pub amount: i16,
+    pub damage_type: DamageType,
+}
+
+impl Damage {
+    pub fn new(amount: i16, damage_type: DamageType) -> Self {
+        Self {
+            amount,
+            damage_type,
+        }
+    }
+}
+
+/// Scales incoming damage by type. Below 1 resists it, above 1 is a weakness.
+#[derive(Component, Clone, Debug)]
+pub struct Resistances {
+    multipliers: [f32; DamageType::COUNT],
+    /// Scaled damage smaller than a whole hitpoint, carried over to the next hit.
+    damage_remainder: f32,
+}
+
+impl Default for Resistances {
+    fn default() -> Self {
+        Self {
+            multipliers: [1.; DamageType::COUNT],
+            damage_remainder: 0.,
+        }
+    }
+}
+
+impl Resistances {
+    pub fn with(mut self, damage_type: DamageType, multiplier: f32) -> Self {
+        self.multipliers[damage_type as usize] = multiplier;
+        self
+    }
+
+    fn apply(&mut self, damage: Damage, multiplier: f32) -> i16 {
+        let scaled =
+            damage.amount as f32 * self.multipliers[damage.damage_type as usize] * multiplier
+                + self.damage_remainder;
+
+        self.damage_remainder = scaled.fract();
+        scaled.trunc() as i16
+    }
+}
+
+/// Ignores all damage for a moment after each hit that got through.
+#[derive(Component)]
+pub struct Invulnerability {
+    timer: Timer,
+}
+
+impl Invulnerability {
+    pub fn new(seconds: f32) -> Self {
+        let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+        // Starts out spent so the first hit goes through.
+        timer.tick(timer.duration());
+
+        Self { timer }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.timer.finished()
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.timer.elapsed_secs()
+    }
+
+    pub fn set_seconds(&mut self, seconds: f32) {
+        self.timer.set_duration(Duration::from_secs_f32(seconds));
+    }
+
+    fn start(&mut self) {
+        self.timer.reset();
+    }
+}
+
+/// Asks for `damage` to be dealt to `target`. Every damage source goes through this event
+/// so resistances and shields apply the same way to all of them.
+#[derive(Event)]
+pub struct DamageEvent {
+    pub source: Entity,
+    pub target: Entity,
+    pub damage: Damage,
+    /// Where the damage comes from, used to tell which side the target was hit on.
+    pub source_position: Vec2,
+}
+
+impl DamageEvent {
+    pub fn new(source: Entity, target: Entity, damage: Damage, source_position: Vec2) -> Self {
+        Self {
+            source,
+            target,
+            damage,
+            source_position,
+        }
+    }
+}
+
+/// Sent whenever an entity loses health so feedback systems can react to it.
+/// Running an entity other than the player out of health also sends a [`DeathEvent`].
+#[derive(Event)]
+pub struct DamageDealt {
+    pub source: Entity,
+    pub target: Entity,
+    pub damage_type: DamageType,
+    pub amount: i16,
+    pub source_position: Vec2,
+}
+
+fn tick_invulnerability(mut query: Query<&mut Invulnerability>, time: Res<Time>) {
+    for mut invulnerability in &mut query {
+        invulnerability.timer.tick(time.delta());
+    }
+}
+
+fn resolve_damage(
+    mut damage_event_reader: EventReader<DamageEvent>,
+    mut damage_dealt_event_writer: EventWriter<DamageDealt>,
+    mut death_event_writer: EventWriter<DeathEvent>,
+    mut target_query: Query<
+        (
+            &Transform,
+            &mut ResourcePool<Health>,
+            Option<&mut Resistances>,
+            Option<&mut Shield>,
+            Option<&mut Invulnerability>,
+            Has<Player>,
+        ),
+        Without<Dying>,
+    >,
+    status_modifiers_query: Query<&StatusModifiers>,
+    water_barriers: WaterBarriers,
+) {
+    for &DamageEvent {
+        source,
+        target,
+        damage,
+        source_position,
+    } in damage_event_reader.read()
+    {
+        let Ok((transform, mut health, resistances, shield, invulnerability, is_player)) =
+            target_query.get_mut(target)
+        else {
+            continue;
+        };
+
+        if health.is_empty() || invulnerability.as_ref().is_some_and(|i| i.is_active()) {
+            continue;
+        }
+
+        // Barriers only shelter the mage's allies from the dragon's fire.
+        if !is_player
+            && damage.damage_type == DamageType::Fire
+            && water_barriers.shelter(source_position, transform.translation.xy())
+        {
+            continue;
+        }
+
+        // Status effects on either side scale the damage on top of the target's resistances.
+        let multiplier = status_modifiers_query
+            .get(source)
+            .map_or(1., StatusModifiers::damage_dealt)
+            * status_modifiers_query
+                .get(target)
+                .map_or(1., |modifiers| modifiers.damage_taken(damage.damage_type));
+
+        let mut amount = match resistances {
+            Some(mut resistances) => resistances.apply(damage, multiplier),
+            None => (damage.amount as f32 * multiplier).round() as i16,
+        };
+
+        if let Some(mut shield) = shield {
+            let incoming = transform.translation.xy() - source_position;
+            amount = shield.absorb(amount, incoming);
+        }
+
+        if amount <= 0 {
+            continue;
+        }
+
+        health.subtract(amount);
+        if let Some(mut invulnerability) = invulnerability {
+            invulnerability.start();
+        }
+        damage_dealt_event_writer.write(DamageDealt {
+            source,
+            target,
+            damage_type: damage.damage_type,
+            amount,
+            source_position,
+        });
+
+        // The player running out of health ends the run instead.
+        if health.is_empty() && !is_player {
+            death_event_writer.write(DeathEvent::new(
+                target,
+                DeathCause::Slain,
+                Some(source),
+                transform.translation.xy(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::{PlaySoundEffectEvent, PlaybackSettings},
+    playing,
+};
+
+use super::{
+    combat::Projectile, entity_pool::EntityPool, game_timer::GameTimer, level::Building,
+    siege::SiegeEngine, Enemy,
+};
+
+/// Long enough for the death clip to play out and its last frame to linger for a moment.
+const DEATH_SECONDS: f32 = 0.8;
+
+pub(super) struct DeathPlugin;
+
+impl Plugin for DeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DeathEvent>();
+
+        app.add_systems(FixedUpdate, finish_dying.run_if(playing()));
+        // Cleanup runs once every gameplay system had its say about this tick,
+        // after subscribers that need to look at the dead entity.
+        app.add_systems(
+            FixedPostUpdate,
+            (play_death_sounds, despawn_entities)
+                .chain()
+                .run_if(playing()),
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeathCause {
+    /// Health ran out.
+    Slain,
+    /// The fire burned down before anything brought the entity's health to zero.
+    BurnedOut,
+    /// Burned down by the breath while running for water.
+    Finished,
+}
+
+/// Sent when an entity dies, before it plays its death animation and is cleaned up.
+#[derive(Event)]
+pub struct DeathEvent {
+    pub entity: Entity,
+    pub cause: DeathCause,
+    /// Whoever dealt the killing blow, if anyone did.
+    pub killer: Option<Entity>,
+    pub position: Vec2,
+}
+
+impl DeathEvent {
+    pub fn new(entity: Entity, cause: DeathCause, killer: Option<Entity>, position: Vec2) -> Self {
+        Self {
+            entity,
+            cause,
+            killer,
+            position,
+        }
+    }
+}
+
+/// Takes an entity out of the fight while its death animation plays, then despawns it.
+#[derive(Component)]
+#[require(GameTimer::<Dying>::from_seconds_once(DEATH_SECONDS))]
+pub struct Dying;
+
+/// Pulled back from a death that was already scored, after being slain and set alight.
+/// Dying again pays nothing, so a unit can't be farmed by letting it be saved.
+#[derive(Component)]
+pub struct Rescued;
+
+/// Removes the entity at the end of the tick, handing it back to its pool if it came from one.
+#[derive(Component)]
+pub struct Despawn;
+
+fn finish_dying(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut GameTimer<Dying>), With<Dying>>,
+    time: Res<Time>,
+) {
+    for (entity, mut timer) in &mut query {
+        if timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).try_insert(Despawn);
+        }
+    }
+}
+
+fn play_death_sounds(
+    mut death_event_reader: EventReader<DeathEvent>,
+    mut play_sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    query: Query<(), Or<(With<Building>, With<SiegeEngine>)>>,
+) {
+    for death_event in death_event_reader.read() {
+        // Only structures make enough noise coming down to be heard over the breath.
+        if !query.contains(death_event.entity) {
+            continue;
+        }
+
+        play_sound_effect_event_writer.write(PlaySoundEffectEvent::new(
+            "breathend.ogg",
+            Some(PlaybackSettings {
+                playback_rate: 0.5,
+                volume: 0.6,
+                ..default()
+            }),
+            None,
+        ));
+    }
+}
+
+fn despawn_entities(
+    mut commands: Commands,
+    mut enemy_pool: ResMut<EntityPool<Enemy>>,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
+    query: Query<(Entity, Has<Enemy>, Has<Building>, Has<Projectile>), With<Despawn>>,
+) {
+    for (entity, enemy, building, projectile) in &query {
+        if enemy && !building {
+            enemy_pool.release(&mut commands, entity);
+        } else if projectile {
+            projectile_pool.release(&mut commands, entity);
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+}
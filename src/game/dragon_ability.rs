@@ -0,0 +1,262 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    audio::{PlaySoundEffectEvent, PlaybackSettings},
+    camera::CameraShakeEvent,
+    input::actions::{Dive, Roar, TailSwipe},
+    playing,
+};
+
+use super::{
+    altitude::Altitude,
+    combat::{Homing, Projectile},
+    damage::{Damage, DamageEvent, DamageType},
+    game_timer::GameTimer,
+    hit_feedback::Knockback,
+    spatial_index::{SpatialIndex, SpatialLayer},
+    status_effect::{ApplyStatusEffect, FRIGHTENED},
+    Player, PROJECTILE_GROUP, TILE_SIZE,
+};
+
+const DIVE_SECONDS: f32 = 0.4;
+const DIVE_RADIUS: f32 = TILE_SIZE.x * 3.;
+const DIVE_DAMAGE: i16 = 40;
+const DIVE_KNOCKBACK_SPEED: f32 = 250.;
+const TAIL_SWIPE_RADIUS: f32 = TILE_SIZE.x * 2.5;
+const TAIL_SWIPE_DAMAGE: i16 = 10;
+const TAIL_SWIPE_KNOCKBACK_SPEED: f32 = 400.;
+const ROAR_RADIUS: f32 = TILE_SIZE.x * 8.;
+
+pub(super) struct DragonAbilityPlugin;
+
+impl Plugin for DragonAbilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (tick_ability_cooldowns, land_dives).run_if(playing()),
+        );
+
+        app.add_observer(start_dive);
+        app.add_observer(swipe_tail);
+        app.add_observer(roar);
+    }
+}
+
+/// Moves the dragon can make besides breathing fire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragonAbility {
+    /// Drops onto the ground, hurting and scattering the units it lands among.
+    Dive,
+    /// Knocks back the enemies around the dragon and bats away incoming projectiles.
+    TailSwipe,
+    /// Frightens the units around the dragon into running away.
+    Roar,
+}
+
+impl DragonAbility {
+    pub const ALL: [Self; 3] = [Self::Dive, Self::TailSwipe, Self::Roar];
+
+    /// Seconds before the ability can be used again once it was used.
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            Self::Dive => 6.,
+            Self::TailSwipe => 3.,
+            Self::Roar => 12.,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dive => "Dive",
+            Self::TailSwipe => "Tail",
+            Self::Roar => "Roar",
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct AbilityCooldowns([Timer; DragonAbility::ALL.len()]);
+
+impl Default for AbilityCooldowns {
+    fn default() -> Self {
+        Self(DragonAbility::ALL.map(|ability| {
+            let mut timer = Timer::from_seconds(ability.cooldown(), TimerMode::Once);
+            // Every ability is ready at the start of a run.
+            timer.tick(timer.duration());
+            timer
+        }))
+    }
+}
+
+impl AbilityCooldowns {
+    pub fn remaining_secs(&self, ability: DragonAbility) -> f32 {
+        self.0[ability as usize].remaining_secs()
+    }
+
+    /// Starts the cooldown and returns true if the ability was ready.
+    fn try_use(&mut self, ability: DragonAbility) -> bool {
+        let timer = &mut self.0[ability as usize];
+
+        if !timer.finished() {
+            return false;
+        }
+
+        timer.reset();
+        true
+    }
+}
+
+/// The dragon is on its way down and lands once the timer runs out. Its altitude drops to the
+/// ground meanwhile, whatever height it dove from.
+#[derive(Component)]
+#[require(GameTimer::<Diving>::from_seconds_once(DIVE_SECONDS))]
+pub struct Diving;
+
+fn tick_ability_cooldowns(mut cooldowns: Single<&mut AbilityCooldowns>, time: Res<Time>) {
+    for timer in &mut cooldowns.0 {
+        timer.tick(time.delta());
+    }
+}
+
+fn start_dive(
+    _trigger: Trigger<Started<Dive>>,
+    mut commands: Commands,
+    player: Single<(Entity, &mut AbilityCooldowns), (With<Player>, Without<Diving>)>,
+) {
+    let (player_entity, mut cooldowns) = player.into_inner();
+
+    if cooldowns.try_use(DragonAbility::Dive) {
+        commands.entity(player_entity).insert(Diving);
+    }
+}
+
+fn land_dives(
+    mut commands: Commands,
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut camera_shake_event_writer: EventWriter<CameraShakeEvent>,
+    player: Single<(Entity, &Transform, &mut GameTimer<Diving>), With<Diving>>,
+    spatial_index: Res<SpatialIndex>,
+    time: Res<Time>,
+) {
+    let (player_entity, transform, mut timer) = player.into_inner();
+
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    commands
+        .entity(player_entity)
+        .remove::<(Diving, GameTimer<Diving>)>();
+
+    let position = transform.translation.xy();
+
+    for (target, target_position) in
+        spatial_index.query_radius(SpatialLayer::Enemies, position, DIVE_RADIUS)
+    {
+        damage_event_writer.write(DamageEvent::new(
+            player_entity,
+            target,
+            Damage::new(DIVE_DAMAGE, DamageType::Blunt),
+            position,
+        ));
+        commands.entity(target).try_insert(Knockback::new(
+            (target_position - position).normalize_or_zero() * DIVE_KNOCKBACK_SPEED,
+        ));
+    }
+
+    camera_shake_event_writer.write(CameraShakeEvent::new(0.5));
+}
+
+/// Only reaches what is around the dragon while it flies low. Projectiles caught by the tail
+/// are sent back the way they came and can't hurt anyone anymore.
+fn swipe_tail(
+    _trigger: Trigger<Started<TailSwipe>>,
+    mut commands: Commands,
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut camera_shake_event_writer: EventWriter<CameraShakeEvent>,
+    player: Single<(Entity, &Transform, &Altitude, &mut AbilityCooldowns), With<Player>>,
+    mut projectile_query: Query<
+        (&mut Transform, &mut Velocity),
+        (With<Projectile>, Without<Player>),
+    >,
+    spatial_index: Res<SpatialIndex>,
+) {
+    let (player_entity, player_transform, altitude, mut cooldowns) = player.into_inner();
+
+    if !altitude.is_low() || !cooldowns.try_use(DragonAbility::TailSwipe) {
+        return;
+    }
+
+    let position = player_transform.translation.xy();
+
+    for (target, target_position) in
+        spatial_index.query_radius(SpatialLayer::Enemies, position, TAIL_SWIPE_RADIUS)
+    {
+        damage_event_writer.write(DamageEvent::new(
+            player_entity,
+            target,
+            Damage::new(TAIL_SWIPE_DAMAGE, DamageType::Blunt),
+            position,
+        ));
+        commands.entity(target).try_insert(Knockback::new(
+            (target_position - position).normalize_or_zero() * TAIL_SWIPE_KNOCKBACK_SPEED,
+        ));
+    }
+
+    for (projectile, projectile_position) in
+        spatial_index.query_radius(SpatialLayer::Projectiles, position, TAIL_SWIPE_RADIUS)
+    {
+        let Ok((mut transform, mut velocity)) = projectile_query.get_mut(projectile) else {
+            continue;
+        };
+
+        let away = (projectile_position - position).normalize_or_zero();
+        velocity.linvel = away * velocity.linvel.length();
+        transform.rotation = Quat::from_rotation_z(velocity.linvel.to_angle());
+        commands
+            .entity(projectile)
+            .try_remove::<Homing>()
+            .try_insert(CollisionGroups::new(PROJECTILE_GROUP, Group::NONE));
+    }
+
+    camera_shake_event_writer.write(CameraShakeEvent::new(0.2));
+}
+
+fn roar(
+    _trigger: Trigger<Started<Roar>>,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffect>,
+    mut camera_shake_event_writer: EventWriter<CameraShakeEvent>,
+    mut play_sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    player: Single<(Entity, &Transform, &mut AbilityCooldowns), With<Player>>,
+    spatial_index: Res<SpatialIndex>,
+) {
+    let (player_entity, player_transform, mut cooldowns) = player.into_inner();
+
+    if !cooldowns.try_use(DragonAbility::Roar) {
+        return;
+    }
+
+    for (target, _) in spatial_index.query_radius(
+        SpatialLayer::Enemies,
+        player_transform.translation.xy(),
+        ROAR_RADIUS,
+    ) {
+        apply_status_effect_event_writer.write(ApplyStatusEffect::new(
+            target,
+            FRIGHTENED,
+            Some(player_entity),
+        ));
+    }
+
+    camera_shake_event_writer.write(CameraShakeEvent::new(0.3));
+    play_sound_effect_event_writer.write(PlaySoundEffectEvent::new(
+        "breathstart.ogg",
+        Some(PlaybackSettings {
+            playback_rate: 0.4,
+            ..default()
+        }),
+        None,
+    ));
+}
@@ -4,10 +4,10 @@ use rand::{
     seq::{IndexedRandom as _, IteratorRandom},
     Rng,
 };
-use std::{collections::HashMap, time::Duration};
+use std::time::Duration;
 
 use crate::{
-    animation::{AnimationIndices, AnimationTimer},
+    animation::{AnimationFrameEvent, SpriteAnimation},
     camera::{RenderLayer, YSorted},
     game::{combat::OnFire, game_timer::GameTimer},
     physics::Speed,
@@ -15,44 +15,43 @@ use crate::{
 };
 
 use super::{
-    combat::{AttackDamage, AttackTimer, Range, SpawnProjectileEvent},
+    combat::{
+        AttackDamage, AttackProjectile, AttackTimer, ProjectileKind, Range, SpawnProjectileEvent,
+    },
+    damage::{Damage, DamageType, Resistances},
+    death::{DeathEvent, Dying},
+    entity_pool::EntityPool,
+    fire_contagion::{FireSpread, Flammable},
+    mage::Mage,
+    population::EnemyPopulation,
     resource_pool::{Health, ResourcePool},
+    shield::Shield,
+    siege::{siege_engine_bundle, SiegeEngine},
+    spatial_index::{SpatialIndex, SpatialLayer},
+    status_effect::{
+        ApplyStatusEffect, Control, StatusEffects, StatusModifiers, BURNING, FRIGHTENED,
+    },
+    steering::{self, SteeringSettings},
     BorderTile, Player, BUILDING_GROUP, ENEMY_GROUP, FIRE_BREATH_GROUP, HALF_TILE_SIZE, TILE_SIZE,
 };
 
-#[derive(Resource, Deref)]
-struct AnimationTagMap(HashMap<AnimationTag, [usize; 2]>);
+/// Every this many spawns a siege engine is spawned instead of a foot soldier.
+const SIEGE_ENGINE_SPAWN_INTERVAL: u32 = 15;
 
-impl Default for AnimationTagMap {
-    fn default() -> Self {
-        Self(HashMap::from([
-            (AnimationTag::RunLeft, [0, 7]),
-            (AnimationTag::RunUpLeft, [16, 23]),
-            (AnimationTag::RunUp, [32, 39]),
-            (AnimationTag::RunUpRight, [48, 55]),
-            (AnimationTag::RunRight, [64, 71]),
-            (AnimationTag::RunDownRight, [80, 87]),
-            (AnimationTag::RunDown, [96, 103]),
-            (AnimationTag::RunDownLeft, [112, 119]),
-            // TODO update all indexes
-            (AnimationTag::AttackLeft, [12, 15]),
-            (AnimationTag::AttackUpLeft, [28, 31]),
-            (AnimationTag::AttackUp, [44, 47]),
-            (AnimationTag::AttackUpRight, [60, 63]),
-            (AnimationTag::AttackRight, [76, 79]),
-            (AnimationTag::AttackDownRight, [92, 95]),
-            (AnimationTag::AttackDown, [108, 111]),
-            (AnimationTag::AttackDownLeft, [124, 127]),
-        ]))
-    }
-}
+const RUN_CLIP: &str = "run";
+const ATTACK_CLIP: &str = "attack";
+const DEATH_CLIP: &str = "death";
+/// Frame event on which an attacking soldier lets its projectile go.
+const RELEASE_EVENT: &str = "release";
+/// Units this close to one that dies lose their nerve for a moment.
+const FRIGHTEN_RADIUS: f32 = TILE_SIZE.x * 3.;
 
 pub(super) struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(EnemySpawnTimer::new(3.));
-        app.insert_resource(AnimationTagMap::default());
+        app.init_resource::<SteeringSettings>();
 
         app.add_systems(
             OnEnter(AppState::InGame),
@@ -64,69 +63,102 @@ impl Plugin for EnemyPlugin {
             (
                 spawn_enemies,
                 handle_enemy_movement,
-                handle_enemy_attacks,
-                update_enemy_animation_tag.after(handle_enemy_movement),
-                update_enemy_animation_indexes.after(update_enemy_animation_tag),
+                handle_enemy_attacks.before(update_enemy_animations),
+                frighten_nearby_units,
+                update_enemy_animations.after(handle_enemy_movement),
             )
                 .run_if(playing()),
         );
 
+        app.add_observer(release_enemy_attacks);
         app.add_observer(on_add_on_fire);
+        app.add_observer(on_remove_on_fire);
+        app.add_observer(on_add_dying);
     }
 }
 
-#[derive(Component, Eq, PartialEq, Hash, Debug, Copy, Clone)]
-pub enum AnimationTag {
-    RunLeft,
-    RunUpLeft,
-    RunUp,
-    RunUpRight,
-    RunRight,
-    RunDownRight,
-    RunDown,
-    RunDownLeft,
-    AttackLeft,
-    AttackUpLeft,
-    AttackUp,
-    AttackUpRight,
-    AttackRight,
-    AttackDownRight,
-    AttackDown,
-    AttackDownLeft,
+#[derive(Component)]
+#[require(StatusEffects)]
+pub struct Enemy;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FootSoldier {
+    Archer,
+    Crossbowman,
+    Axeman,
+    Knight,
+    Mage,
 }
 
-impl AnimationTag {
-    pub fn current_attack_tag(dir: Dir2) -> Option<Self> {
-        match dir {
-            Dir2::NORTH => Some(Self::AttackUp),
-            Dir2::EAST => Some(Self::AttackRight),
-            Dir2::SOUTH => Some(Self::AttackDown),
-            Dir2::WEST => Some(Self::AttackLeft),
-            Dir2::NORTH_EAST => Some(Self::AttackUpRight),
-            Dir2::NORTH_WEST => Some(Self::AttackUpLeft),
-            Dir2::SOUTH_EAST => Some(Self::AttackDownRight),
-            Dir2::SOUTH_WEST => Some(Self::AttackDownLeft),
-            _ => None,
+impl FootSoldier {
+    fn texture_path(&self) -> &'static str {
+        match self {
+            Self::Archer | Self::Crossbowman | Self::Mage => "textures/enemy_archer.png",
+            Self::Axeman | Self::Knight => "textures/enemy_axe.png",
         }
     }
 
-    pub fn current_run_tag(dir: Dir2) -> Option<Self> {
-        match dir {
-            Dir2::NORTH => Some(Self::RunUp),
-            Dir2::EAST => Some(Self::RunRight),
-            Dir2::SOUTH => Some(Self::RunDown),
-            Dir2::WEST => Some(Self::RunLeft),
-            Dir2::NORTH_EAST => Some(Self::RunUpRight),
-            Dir2::NORTH_WEST => Some(Self::RunUpLeft),
-            Dir2::SOUTH_EAST => Some(Self::RunDownRight),
-            Dir2::SOUTH_WEST => Some(Self::RunDownLeft),
-            _ => None,
+    /// Tint telling apart soldiers that share a sprite sheet.
+    fn color(&self) -> Color {
+        match self {
+            Self::Archer | Self::Axeman => Color::WHITE,
+            Self::Crossbowman => Color::srgb(1.0, 0.85, 0.65),
+            Self::Knight => Color::srgb(0.7, 0.8, 1.0),
+            Self::Mage => Color::srgb(0.75, 0.55, 1.0),
         }
     }
-}
 
-#[derive(Component)]
-pub struct Enemy;
+    fn health(&self) -> i16 {
+        match self {
+            Self::Archer => 30,
+            Self::Crossbowman => 40,
+            Self::Axeman => 60,
+            Self::Knight => 80,
+            Self::Mage => 40,
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            Self::Archer | Self::Axeman | Self::Mage => 100.,
+            Self::Crossbowman => 85.,
+            Self::Knight => 70.,
+        }
+    }
+
+    /// Chance of catching fire from a burning ally, armor and water magic making it less likely.
+    fn ignition_chance(&self) -> f32 {
+        match self {
+            Self::Archer | Self::Crossbowman => 0.4,
+            Self::Axeman => 0.35,
+            Self::Knight => 0.15,
+            Self::Mage => 0.05,
+        }
+    }
+
+    /// Armor shrugs off arrows and blows, while a mage's wards soften fire and magic.
+    fn resistances(&self) -> Resistances {
+        match self {
+            Self::Archer | Self::Crossbowman => Resistances::default().with(DamageType::Fire, 1.25),
+            Self::Axeman => Resistances::default(),
+            Self::Knight => Resistances::default()
+                .with(DamageType::Piercing, 0.5)
+                .with(DamageType::Blunt, 0.75),
+            Self::Mage => Resistances::default()
+                .with(DamageType::Fire, 0.75)
+                .with(DamageType::Magic, 0.5),
+        }
+    }
+
+    fn follow_distance(&self) -> f32 {
+        match self {
+            Self::Archer | Self::Axeman | Self::Knight => TILE_SIZE.x * 6.,
+            Self::Crossbowman => TILE_SIZE.x * 8.,
+            // Mages keep behind the front line
+            Self::Mage => TILE_SIZE.x * 10.,
+        }
+    }
+}
 
 #[derive(Resource)]
 struct EnemySpawnCounter(u32);
@@ -140,12 +172,35 @@ impl EnemySpawnTimer {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub enum Behavior {
-    FollowPlayer { distance: f32 },
+    FollowPlayer {
+        distance: f32,
+    },
     Random,
+    /// Runs straight for water to put out the fire it is in.
+    SeekWater {
+        target: Vec2,
+    },
+}
+
+/// Where an enemy is within its attack animation, so projectiles leave on the release frame.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackPhase {
+    #[default]
+    Ready,
+    WindUp,
+    Recover,
 }
 
+/// Behavior an entity had before catching fire, restored if the fire is put out.
+#[derive(Component)]
+pub struct BehaviorBeforeFire(Behavior);
+
+/// Flames drawn over a burning entity.
+#[derive(Component)]
+pub struct OnFireVisual;
+
 #[derive(Component, Deref, DerefMut)]
 pub struct FacingDirection(Dir2);
 
@@ -175,22 +230,31 @@ fn load_atlas_handlers(mut commands: Commands, asset_server: Res<AssetServer>) {
 
 fn spawn_enemies(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
     time: Res<Time>,
     mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
     mut enemy_spawn_counter: ResMut<EnemySpawnCounter>,
     tile_query: Query<&Transform, With<BorderTile>>,
-    (texture_archer_atlas_handle, texture_axeman_atlas_handle): (
+    (asset_server, texture_archer_atlas_handle, texture_axeman_atlas_handle): (
+        Res<AssetServer>,
         Res<TextureArcherAtlasHandle>,
         Res<TextureAxeAtlasHandle>,
     ),
+    (mut enemy_pool, enemy_population, live_enemy_query): (
+        ResMut<EntityPool<Enemy>>,
+        Res<EnemyPopulation>,
+        Query<(), (With<Enemy>, With<Behavior>)>,
+    ),
 ) {
     let duration = enemy_spawn_timer.duration();
 
     if enemy_spawn_timer.tick(time.delta()).just_finished() {
+        if live_enemy_query.iter().count() >= enemy_population.max_live_enemies {
+            return;
+        }
+
         enemy_spawn_counter.0 = enemy_spawn_counter.0.wrapping_add(1);
 
-        if enemy_spawn_counter.0 % 10 == 0 {
+        if enemy_spawn_counter.0.is_multiple_of(10) {
             enemy_spawn_timer.set_duration(Duration::from_secs_f32(
                 1.0_f32.max(duration.as_secs_f32() - 0.5),
             ));
@@ -200,56 +264,108 @@ fn spawn_enemies(
         if let Some(tile_transform) = tile_query.iter().choose(&mut rng) {
             let translation = tile_transform.translation.xy().extend(1.);
 
-            //pick a random texture atlas handle between archer and axe
-            let (texture_atlas_handle, image) = if rng.random_bool(0.5) {
-                (
-                    texture_archer_atlas_handle.0.clone(),
-                    asset_server.load("textures/enemy_archer.png"),
-                )
-            } else {
-                (
-                    texture_axeman_atlas_handle.0.clone(),
-                    asset_server.load("textures/enemy_axe.png"),
-                )
+            if enemy_spawn_counter
+                .0
+                .is_multiple_of(SIEGE_ENGINE_SPAWN_INTERVAL)
+            {
+                let siege_engine = if rng.random_bool(0.5) {
+                    SiegeEngine::Ballista
+                } else {
+                    SiegeEngine::Catapult
+                };
+
+                enemy_pool
+                    .acquire(&mut commands)
+                    .insert(siege_engine_bundle(siege_engine, translation));
+                return;
+            }
+
+            let foot_soldier = match rng.random_range(0..7) {
+                0 | 1 => FootSoldier::Archer,
+                2 | 3 => FootSoldier::Axeman,
+                4 => FootSoldier::Crossbowman,
+                5 => FootSoldier::Knight,
+                _ => FootSoldier::Mage,
+            };
+            let texture_atlas_handle = match foot_soldier {
+                FootSoldier::Archer | FootSoldier::Crossbowman | FootSoldier::Mage => {
+                    texture_archer_atlas_handle.0.clone()
+                }
+                FootSoldier::Axeman | FootSoldier::Knight => texture_axeman_atlas_handle.0.clone(),
             };
 
-            commands
-                .spawn((
-                    Sprite {
-                        image,
-                        texture_atlas: Some(TextureAtlas {
-                            layout: texture_atlas_handle,
-                            index: 4,
-                        }),
-                        ..Default::default()
-                    },
-                    Transform::from_translation(translation),
-                    AttackDamage(5),
-                    AttackTimer::new(3.),
-                    Behavior::FollowPlayer {
-                        distance: TILE_SIZE.x * 6.,
-                    },
-                    ResourcePool::<Health>::new(1),
-                    Enemy,
-                    Range(TILE_SIZE.x * 15.),
-                    Speed(100.),
-                    RenderLayers::layer(RenderLayer::Ground.into()),
-                    FacingDirection::default(),
-                    StateScoped(AppState::GameOver),
-                    YSorted,
-                ))
-                .insert((
-                    AnimationIndices::new(4, 11),
-                    AnimationTimer::from_seconds(0.2),
-                    AnimationTag::RunLeft,
-                    Collider::cuboid(HALF_TILE_SIZE.x, HALF_TILE_SIZE.y),
-                    RigidBody::Dynamic,
-                    CollisionGroups::new(
-                        ENEMY_GROUP,
-                        ENEMY_GROUP | BUILDING_GROUP | FIRE_BREATH_GROUP,
-                    ),
-                    LockedAxes::ROTATION_LOCKED,
-                ));
+            let mut enemy_entity = enemy_pool.acquire(&mut commands);
+            enemy_entity.insert((
+                Sprite {
+                    color: foot_soldier.color(),
+                    image: asset_server.load(foot_soldier.texture_path()),
+                    texture_atlas: Some(TextureAtlas {
+                        layout: texture_atlas_handle,
+                        index: 4,
+                    }),
+                    ..Default::default()
+                },
+                Transform::from_translation(translation),
+                Behavior::FollowPlayer {
+                    distance: foot_soldier.follow_distance(),
+                },
+                ResourcePool::<Health>::new(foot_soldier.health()),
+                Enemy,
+                Range(TILE_SIZE.x * 15.),
+                Speed(foot_soldier.speed()),
+                RenderLayers::layer(RenderLayer::Ground.into()),
+                FacingDirection::default(),
+                StateScoped(AppState::GameOver),
+                YSorted,
+            ));
+            enemy_entity.insert((
+                AttackPhase::default(),
+                Flammable::new(foot_soldier.ignition_chance()),
+                foot_soldier.resistances(),
+                SpriteAnimation::new(asset_server.load("animations/soldier.anim.ron"), RUN_CLIP),
+                Collider::cuboid(HALF_TILE_SIZE.x, HALF_TILE_SIZE.y),
+                RigidBody::Dynamic,
+                CollisionGroups::new(
+                    ENEMY_GROUP,
+                    ENEMY_GROUP | BUILDING_GROUP | FIRE_BREATH_GROUP,
+                ),
+                LockedAxes::ROTATION_LOCKED,
+            ));
+
+            match foot_soldier {
+                FootSoldier::Archer => {
+                    enemy_entity.insert((
+                        AttackDamage(Damage::new(5, DamageType::Piercing)),
+                        AttackProjectile(ProjectileKind::Arrow),
+                        AttackTimer::new(3.),
+                    ));
+                }
+                FootSoldier::Crossbowman => {
+                    enemy_entity.insert((
+                        AttackDamage(Damage::new(8, DamageType::Piercing)),
+                        AttackProjectile(ProjectileKind::Quarrel),
+                        AttackTimer::new(4.),
+                    ));
+                }
+                FootSoldier::Axeman => {
+                    enemy_entity.insert((
+                        AttackDamage(Damage::new(5, DamageType::Blunt)),
+                        AttackProjectile(ProjectileKind::ThrowingAxe),
+                        AttackTimer::new(3.),
+                    ));
+                }
+                FootSoldier::Knight => {
+                    enemy_entity.insert((
+                        AttackDamage(Damage::new(5, DamageType::Blunt)),
+                        AttackProjectile(ProjectileKind::ThrowingAxe),
+                        AttackTimer::new(3.),
+                        Shield::default(),
+                    ));
+                }
+                FootSoldier::Mage => {
+                    enemy_entity.insert(Mage);
+                }
+            }
         }
     }
 }
@@ -258,80 +374,134 @@ fn setup_enemy_spawn_counter(mut commands: Commands) {
     commands.insert_resource(EnemySpawnCounter(0));
 }
 
-fn update_enemy_animation_tag(
-    mut enemy_query: Query<
-        (&Transform, &Behavior, &FacingDirection, &mut AnimationTag),
-        With<Enemy>,
-    >,
-    player_transform: Single<&Transform, With<Player>>,
+fn update_enemy_animations(
+    mut enemy_query: Query<(&mut AttackPhase, &FacingDirection, &mut SpriteAnimation), With<Enemy>>,
 ) {
-    let player_position = player_transform.translation.xy();
-
-    for (enemy_transform, enemy_behavior, facing_direction, mut animation_tag) in &mut enemy_query {
-        let current_tag = match enemy_behavior {
-            Behavior::FollowPlayer { distance } => {
-                if enemy_transform.translation.xy().distance(player_position) > *distance {
-                    AnimationTag::current_run_tag(**facing_direction)
-                } else {
-                    AnimationTag::current_attack_tag(**facing_direction)
-                }
-            }
-            Behavior::Random => AnimationTag::current_run_tag(**facing_direction),
-        };
-
-        if let Some(tag) = current_tag {
-            *animation_tag = tag;
+    for (mut attack_phase, facing_direction, mut animation) in &mut enemy_query {
+        animation.set_direction(**facing_direction);
+
+        match *attack_phase {
+            AttackPhase::WindUp => animation.play(ATTACK_CLIP),
+            // The attack clip hands over to running once its recovery frames have played.
+            AttackPhase::Recover if animation.clip() == ATTACK_CLIP => {}
+            AttackPhase::Recover => *attack_phase = AttackPhase::Ready,
+            AttackPhase::Ready => animation.play(RUN_CLIP),
         }
     }
 }
 
-fn update_enemy_animation_indexes(
-    mut enemy_query: Query<
-        (&AnimationTag, &mut AnimationIndices, &mut Sprite),
-        (With<Enemy>, Changed<AnimationTag>),
-    >,
-    animation_tag_map: Res<AnimationTagMap>,
-) {
-    for (animation_tag, mut animation_indices, mut sprite) in &mut enemy_query {
-        if let Some(value) = animation_tag_map.get(animation_tag) {
-            let [first, last] = *value;
-            *animation_indices = AnimationIndices::new(first, last);
-            if let Some(ref mut texture_atlas) = sprite.texture_atlas {
-                texture_atlas.index = first;
-            }
-        };
-    }
-}
-
 fn handle_enemy_movement(
     mut enemy_query: Query<
         (
+            Entity,
             &mut Transform,
             &mut FacingDirection,
             Option<&mut GameTimer<Behavior>>,
             &Speed,
             &Behavior,
+            &AttackPhase,
+            Option<&StatusModifiers>,
         ),
         With<Enemy>,
     >,
     player_transform: Single<&Transform, (With<Player>, Without<Enemy>)>,
+    spatial_index: Res<SpatialIndex>,
+    steering_settings: Res<SteeringSettings>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_secs();
     let player_position = player_transform.translation.xy();
 
-    for (mut enemy_transform, mut facing_direction, behavior_timer, enemy_speed, enemy_behavior) in
-        &mut enemy_query
+    for (
+        enemy_entity,
+        mut enemy_transform,
+        mut facing_direction,
+        behavior_timer,
+        enemy_speed,
+        enemy_behavior,
+        attack_phase,
+        status_modifiers,
+    ) in &mut enemy_query
     {
+        match status_modifiers.and_then(StatusModifiers::control) {
+            Some(Control::Stunned) => continue,
+            Some(Control::Frightened) => {
+                let enemy_position = enemy_transform.translation.xy();
+                let flee_direction =
+                    Dir2::new(enemy_position - player_position).unwrap_or(**facing_direction);
+
+                enemy_transform.translation.x += flee_direction.x * enemy_speed.0 * delta_secs;
+                enemy_transform.translation.y += flee_direction.y * enemy_speed.0 * delta_secs;
+                **facing_direction = flee_direction;
+                continue;
+            }
+            None => {}
+        }
+
         match enemy_behavior {
             Behavior::FollowPlayer { distance } => {
                 let enemy_position = enemy_transform.translation.xy();
-                let new_direction = Dir2::new(player_position - enemy_position).unwrap_or(Dir2::X);
+                let player_direction =
+                    Dir2::new(player_position - enemy_position).unwrap_or(Dir2::X);
+                let player_distance = enemy_position.distance(player_position);
+
+                let is_attacking = *attack_phase != AttackPhase::Ready;
+                // Attacking units hold their ground so the telegraph reads clearly.
+                let seek = if is_attacking {
+                    Vec2::ZERO
+                } else if player_distance > *distance {
+                    *player_direction
+                } else if player_distance < *distance * steering_settings.ring_tolerance {
+                    -*player_direction
+                } else {
+                    Vec2::ZERO
+                };
+                let separation = steering::separation(
+                    enemy_entity,
+                    enemy_position,
+                    &spatial_index,
+                    steering_settings.separation_radius,
+                );
+                let cohesion = steering::cohesion(
+                    enemy_entity,
+                    enemy_position,
+                    &spatial_index,
+                    steering_settings.cohesion_radius,
+                );
+                let avoidance = steering::obstacle_avoidance(
+                    enemy_position,
+                    seek,
+                    &spatial_index,
+                    steering_settings.avoidance_radius,
+                );
+
+                let steering_force = seek * steering_settings.seek_weight
+                    + separation * steering_settings.separation_weight
+                    + cohesion * steering_settings.cohesion_weight
+                    + avoidance * steering_settings.avoidance_weight;
+                let velocity = steering_force.clamp_length_max(1.) * enemy_speed.0;
+
+                // Small residual forces would make units jitter in place once they settle.
+                if velocity.length() > enemy_speed.0 * 0.1 {
+                    enemy_transform.translation.x += velocity.x * delta_secs;
+                    enemy_transform.translation.y += velocity.y * delta_secs;
+                }
 
-                if enemy_position.distance(player_position) > *distance {
-                    enemy_transform.translation.x += new_direction.x * enemy_speed.0 * delta_secs;
-                    enemy_transform.translation.y += new_direction.y * enemy_speed.0 * delta_secs;
-                    **facing_direction = new_direction;
+                if !is_attacking && player_distance > *distance {
+                    **facing_direction = Dir2::new(velocity).unwrap_or(player_direction);
+                } else {
+                    **facing_direction = player_direction;
+                }
+            }
+            Behavior::SeekWater { target } => {
+                let enemy_position = enemy_transform.translation.xy();
+                let velocity = (*target - enemy_position).clamp_length_max(enemy_speed.0);
+
+                enemy_transform.translation.x += velocity.x * delta_secs;
+                enemy_transform.translation.y += velocity.y * delta_secs;
+
+                if let Ok(direction) = Dir2::new(velocity) {
+                    **facing_direction = direction;
                 }
             }
             Behavior::Random => {
@@ -361,7 +531,16 @@ fn handle_enemy_movement(
 fn handle_enemy_attacks(
     mut spawn_projectile_event_writer: EventWriter<SpawnProjectileEvent>,
     mut enemy_query: Query<
-        (Entity, &Transform, &mut AttackTimer, &Range, &AttackDamage),
+        (
+            Entity,
+            &Transform,
+            &mut AttackTimer,
+            &Range,
+            &AttackDamage,
+            &AttackProjectile,
+            Option<&mut AttackPhase>,
+            Option<&StatusModifiers>,
+        ),
         (With<Enemy>, Without<OnFire>),
     >,
     player_transform: Single<&Transform, (With<Player>, Without<Enemy>)>,
@@ -369,32 +548,99 @@ fn handle_enemy_attacks(
 ) {
     let player_position = player_transform.translation.xy();
 
-    for (enemy_entity, enemy_transform, mut enemy_attack_timer, enemy_range, enemy_attack_damage) in
-        &mut enemy_query
+    for (
+        enemy_entity,
+        enemy_transform,
+        mut enemy_attack_timer,
+        enemy_range,
+        enemy_attack_damage,
+        enemy_attack_projectile,
+        attack_phase,
+        status_modifiers,
+    ) in &mut enemy_query
     {
+        // Stunned or frightened units hold their fire until they come to their senses.
+        if status_modifiers.is_some_and(|modifiers| modifiers.control().is_some()) {
+            continue;
+        }
+
         if enemy_attack_timer.tick(time.delta()).just_finished() {
             let enemy_position = enemy_transform.translation.xy();
 
             if enemy_position.distance(player_position) <= enemy_range.0 {
-                let direction = (player_position - enemy_position).normalize();
-                let emitter = enemy_entity;
-
-                spawn_projectile_event_writer.write(SpawnProjectileEvent::new(
-                    enemy_attack_damage.0,
-                    direction,
-                    emitter,
-                    enemy_position,
-                    800.,
-                ));
+                match attack_phase {
+                    // Animated attackers wind up first and release on a specific frame.
+                    Some(mut attack_phase) => {
+                        if *attack_phase == AttackPhase::Ready {
+                            *attack_phase = AttackPhase::WindUp;
+                        }
+                    }
+                    None => {
+                        spawn_projectile_event_writer.write(
+                            SpawnProjectileEvent::new(
+                                enemy_attack_damage.0,
+                                enemy_entity,
+                                enemy_position,
+                                player_position,
+                            )
+                            .with_kind(enemy_attack_projectile.0),
+                        );
+                    }
+                }
             }
         }
     }
 }
 
+fn release_enemy_attacks(
+    trigger: Trigger<AnimationFrameEvent>,
+    mut spawn_projectile_event_writer: EventWriter<SpawnProjectileEvent>,
+    mut enemy_query: Query<
+        (
+            &Transform,
+            &AttackDamage,
+            &AttackProjectile,
+            &mut AttackPhase,
+        ),
+        (With<Enemy>, Without<OnFire>),
+    >,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+) {
+    let enemy_entity = trigger.target();
+
+    if trigger.clip != ATTACK_CLIP || trigger.name != RELEASE_EVENT {
+        return;
+    }
+
+    let (
+        Ok((enemy_transform, enemy_attack_damage, enemy_attack_projectile, mut attack_phase)),
+        Ok(player_transform),
+    ) = (enemy_query.get_mut(enemy_entity), player_query.single())
+    else {
+        return;
+    };
+
+    if *attack_phase != AttackPhase::WindUp {
+        return;
+    }
+
+    spawn_projectile_event_writer.write(
+        SpawnProjectileEvent::new(
+            enemy_attack_damage.0,
+            enemy_entity,
+            enemy_transform.translation.xy(),
+            player_transform.translation.xy(),
+        )
+        .with_kind(enemy_attack_projectile.0),
+    );
+    *attack_phase = AttackPhase::Recover;
+}
+
 fn on_add_on_fire(
     trigger: Trigger<OnAdd, OnFire>,
-    mut query: Query<(Option<&mut Speed>, Option<&mut Behavior>)>,
+    mut query: Query<(Option<&mut Behavior>, Option<&mut AttackPhase>)>,
     mut commands: Commands,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffect>,
     mut texture_atlas_layout: Local<Handle<TextureAtlasLayout>>,
     asset_server: Res<AssetServer>,
 ) {
@@ -408,27 +654,108 @@ fn on_add_on_fire(
         None,
     ));
 
-    if let Ok((speed, behavior)) = query.get_mut(entity) {
-        if let Some(mut speed) = speed {
-            **speed *= 2.0;
-        }
+    apply_status_effect_event_writer.write(ApplyStatusEffect::new(entity, BURNING, None));
+
+    if let Ok((behavior, attack_phase)) = query.get_mut(entity) {
         if let Some(mut behavior) = behavior {
+            commands
+                .entity(entity)
+                .insert(BehaviorBeforeFire(*behavior));
             *behavior = Behavior::Random;
         }
+        if let Some(mut attack_phase) = attack_phase {
+            *attack_phase = AttackPhase::Ready;
+        }
         commands
             .entity(entity)
             .insert(GameTimer::<Behavior>::from_seconds(0.2))
             .with_child((
+                OnFireVisual,
                 Sprite {
                     color: Color::default().with_alpha(0.6),
                     image: asset_server.load("textures/fire_anim.png"),
                     texture_atlas: Some(TextureAtlas::from(texture_atlas_layout.clone())),
                     ..Default::default()
                 },
-                AnimationIndices::new(0, 1),
-                AnimationTimer::from_seconds(0.2),
+                SpriteAnimation::new(asset_server.load("animations/fire.anim.ron"), "burn"),
                 RenderLayers::layer(RenderLayer::Ground.into()),
                 StateScoped(AppState::GameOver),
             ));
     }
 }
+
+/// Undoes what catching fire did to an entity when the fire is put out before it burns down.
+fn on_remove_on_fire(
+    trigger: Trigger<OnRemove, OnFire>,
+    mut query: Query<(
+        Option<&mut StatusEffects>,
+        Option<&mut Behavior>,
+        Option<&BehaviorBeforeFire>,
+        Option<&Children>,
+    )>,
+    on_fire_visual_query: Query<Entity, With<OnFireVisual>>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+
+    let Ok((status_effects, behavior, behavior_before_fire, children)) = query.get_mut(entity)
+    else {
+        return;
+    };
+
+    if let Some(mut status_effects) = status_effects {
+        status_effects.remove(BURNING);
+    }
+    if let (Some(mut behavior), Some(behavior_before_fire)) = (behavior, behavior_before_fire) {
+        *behavior = behavior_before_fire.0;
+    }
+
+    // The entity may be getting despawned, so these commands are allowed to fail.
+    commands
+        .entity(entity)
+        .try_remove::<(BehaviorBeforeFire, FireSpread, GameTimer<Behavior>)>();
+
+    for visual in on_fire_visual_query.iter_many(children.into_iter().flatten()) {
+        commands.entity(visual).try_despawn();
+    }
+}
+
+/// Dying soldiers put out their fire, stop fighting and fall over where they stand.
+fn on_add_dying(
+    trigger: Trigger<OnAdd, Dying>,
+    mut query: Query<&mut SpriteAnimation, With<Enemy>>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+
+    let Ok(mut animation) = query.get_mut(entity) else {
+        return;
+    };
+
+    animation.play(DEATH_CLIP);
+    commands
+        .entity(entity)
+        .remove::<(OnFire, StatusEffects, Behavior, AttackPhase, AttackTimer)>()
+        .insert(ColliderDisabled);
+}
+
+/// Units seeing one of their own fall nearby turn and run for a moment.
+fn frighten_nearby_units(
+    mut death_event_reader: EventReader<DeathEvent>,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffect>,
+    spatial_index: Res<SpatialIndex>,
+) {
+    for death_event in death_event_reader.read() {
+        for (entity, _) in
+            spatial_index.query_radius(SpatialLayer::Enemies, death_event.position, FRIGHTEN_RADIUS)
+        {
+            if entity != death_event.entity {
+                apply_status_effect_event_writer.write(ApplyStatusEffect::new(
+                    entity,
+                    FRIGHTENED,
+                    death_event.killer,
+                ));
+            }
+        }
+    }
+}
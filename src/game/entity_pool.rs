@@ -0,0 +1,66 @@
+use core::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::AppState;
+
+/// Marks an entity parked in an [`EntityPool`], stripped of everything but its transform.
+/// Adding it is the cue for anything still pointing at the entity to let go of it, since the
+/// next unit acquiring it keeps the same id.
+#[derive(Component)]
+pub struct Pooled;
+
+/// Entities kept around after they leave play so spawning `T` can reuse them.
+#[derive(Resource)]
+pub struct EntityPool<T> {
+    free: Vec<Entity>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Default for EntityPool<T> {
+    fn default() -> Self {
+        Self {
+            free: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> EntityPool<T> {
+    /// Returns a pooled entity ready to receive a new bundle, or spawns one if the pool is empty.
+    pub fn acquire<'a>(&mut self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        match self.free.pop() {
+            Some(entity) => {
+                let mut entity_commands = commands.entity(entity);
+                entity_commands
+                    .remove::<Pooled>()
+                    .insert(Visibility::Inherited);
+                entity_commands
+            }
+            None => commands.spawn(StateScoped(AppState::GameOver)),
+        }
+    }
+
+    /// Takes an entity out of play, despawning its children and dropping every other component.
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        if self.free.contains(&entity) {
+            return;
+        }
+
+        commands
+            .entity(entity)
+            .despawn_related::<Children>()
+            .retain::<(Transform, StateScoped<AppState>)>()
+            .insert((Pooled, Visibility::Hidden));
+        self.free.push(entity);
+    }
+
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Forgets every pooled entity, for when they were despawned along with the rest of a run.
+    pub fn clear(&mut self) {
+        self.free.clear();
+    }
+}
@@ -2,22 +2,42 @@ use bevy::{prelude::*, render::view::RenderLayers};
 use bevy_enhanced_input::prelude::*;
 use bevy_enoki::prelude::*;
 use bevy_kira_audio::{Audio, AudioChannel, AudioControl};
-use bevy_rapier2d::prelude::{Collider, CollisionGroups, Sensor};
+use bevy_rapier2d::prelude::{CollisionGroups, Sensor};
+use rand::Rng;
 
 use crate::{
-    animation::AnimationIndices,
+    animation::SpriteAnimation,
     audio::DragonBreathChannel,
     camera::{RenderLayer, YSorted},
-    input::{actions::FireBreath, DefaultInputContext},
+    input::actions::FireBreath,
     playing, AppState,
 };
 
 use super::{
-    combat::ImpactDamage,
+    altitude::Altitude,
+    breath_ability::{
+        BreathAbility, SelectedBreath, CONE_HALF_ANGLE, CONE_MOUTH_HALF_WIDTH, CONE_RANGE,
+        MOUTH_DISTANCE,
+    },
+    breath_charge::{breath_charge, BreathBurst, MIN_RELEASE_CHARGE},
+    combat::DamagePerSecond,
+    damage::DamageType,
+    heat::Heat,
     resource_pool::{Fire, ResourcePool},
-    Player, BUILDING_GROUP, ENEMY_GROUP, FIRE_BREATH_GROUP,
+    Player, BARRIER_GROUP, BUILDING_GROUP, ENEMY_GROUP, FIRE_BREATH_GROUP, PROJECTILE_GROUP,
 };
 
+/// Damage a target standing in the breath takes each second.
+const BREATH_DAMAGE_PER_SECOND: f32 = 60.;
+/// Share of its full range the cone starts out with.
+const CONE_START_GROWTH: f32 = 0.3;
+/// Seconds of sustained breath it takes the cone to reach its full range.
+const CONE_GROWTH_SECONDS: f32 = 0.75;
+/// The breath start sound played faster once the breath is fully charged.
+const CHARGED_SFX_PLAYBACK_RATE: f64 = 1.6;
+/// The breath end sound played slower as the stored heat is let out.
+const RELEASE_SFX_PLAYBACK_RATE: f64 = 0.7;
+
 pub(super) struct FireBreathPlugin;
 
 impl Plugin for FireBreathPlugin {
@@ -26,10 +46,8 @@ impl Plugin for FireBreathPlugin {
 
         app.add_systems(
             FixedUpdate,
-            (
-                consume_fire_breath_resource.run_if(on_event::<SubtractFireResourceEvent>),
-                restore_fire_breath_resource,
-            )
+            consume_fire_breath_resource
+                .run_if(on_event::<SubtractFireResourceEvent>)
                 .run_if(playing()),
         );
 
@@ -53,6 +71,7 @@ impl Plugin for FireBreathPlugin {
         app.add_observer(on_fire_breath_fired);
         app.add_observer(on_fire_breath_canceled);
         app.add_observer(on_fire_breath_completed);
+        app.add_observer(remove_breath_cone);
     }
 }
 
@@ -62,63 +81,103 @@ pub struct SubtractFireResourceEvent;
 #[derive(Deref, Resource)]
 pub struct FireBreathParticleMaterialAsset(Handle<SpriteParticle2dMaterial>);
 
+/// Hit area of the breath, attached to the dragon's mouth for as long as it breathes.
+#[derive(Component)]
+pub struct BreathCone;
+
+/// Grows the cone with sustained breath and fills it with flames, so what is drawn on fire
+/// is what gets hit. The higher the dragon flies, the less the flames hurt once they reach
+/// the ground.
 fn spawn_fire_breath(
-    _trigger: Trigger<Fired<FireBreath>>,
+    trigger: Trigger<Fired<FireBreath>>,
     mut commands: Commands,
     material: Res<FireBreathParticleMaterialAsset>,
     asset_server: Res<AssetServer>,
-    player: Single<(&Transform, &ResourcePool<Fire>), With<Player>>,
+    player: Single<
+        (
+            Entity,
+            &Transform,
+            &ResourcePool<Fire>,
+            &Heat,
+            &SelectedBreath,
+            &Altitude,
+        ),
+        With<Player>,
+    >,
+    mut cone_query: Query<
+        (Entity, &mut Transform, &mut DamagePerSecond),
+        (With<BreathCone>, Without<Player>),
+    >,
 ) {
-    let (player_transform, fire_resource_pool) = player.into_inner();
+    let (player_entity, player_transform, fire_resource_pool, heat, selected_breath, altitude) =
+        player.into_inner();
 
-    if fire_resource_pool.is_empty() {
+    if **selected_breath != BreathAbility::Cone {
+        return;
+    }
+
+    if fire_resource_pool.is_empty() || heat.is_overheated() {
+        for (cone, ..) in &cone_query {
+            commands.entity(cone).despawn();
+        }
         return;
     }
 
+    let growth = CONE_START_GROWTH.lerp(1., (trigger.fired_secs / CONE_GROWTH_SECONDS).min(1.));
+    let damage_per_second = BREATH_DAMAGE_PER_SECOND * altitude.breath_effectiveness();
+    let local_transform = breath_cone_transform(growth, player_transform.scale.x);
+
+    match cone_query.single_mut() {
+        Ok((_, mut cone_transform, mut cone_damage)) => {
+            *cone_transform = local_transform;
+            cone_damage.amount = damage_per_second;
+        }
+        Err(_) => {
+            commands.entity(player_entity).with_child((
+                BreathCone,
+                Fire,
+                Sensor,
+                BreathAbility::Cone.collider(),
+                CollisionGroups::new(
+                    FIRE_BREATH_GROUP,
+                    BARRIER_GROUP | BUILDING_GROUP | ENEMY_GROUP | PROJECTILE_GROUP,
+                ),
+                DamagePerSecond::new(damage_per_second, DamageType::Fire),
+                local_transform,
+            ));
+        }
+    }
+
+    let mut rng = rand::rng();
+    let distance = rng.random_range(0. ..=CONE_RANGE * growth);
+    let half_width = CONE_MOUTH_HALF_WIDTH * growth + distance * CONE_HALF_ANGLE.tan();
     let player_direction = player_transform.rotation.mul_vec3(Vec3::Y).xy();
-    // TODO: replace literal value with player sprite dimensions
-    let fire_position = player_transform.translation.xy() + player_direction * 90.;
-    let damage = 1;
+    let fire_position = player_transform.translation.xy()
+        + player_direction * (MOUTH_DISTANCE + distance)
+        + player_direction.perp() * rng.random_range(-half_width..=half_width);
 
     commands.spawn((
         ParticleSpawner(material.clone()),
         ParticleEffectHandle(asset_server.load("vfx/fire_breath.ron")),
         OneShot::Despawn,
-        Fire,
         RenderLayers::layer(RenderLayer::Ground.into()),
         Transform::from_translation(fire_position.extend(10.0)),
-        Sensor,
-        Collider::ball(25.0),
-        CollisionGroups::new(FIRE_BREATH_GROUP, BUILDING_GROUP | ENEMY_GROUP),
         StateScoped(AppState::GameOver),
-        ImpactDamage(damage),
         YSorted,
     ));
 }
 
-fn consume_fire_breath_resource(
-    mut fire_resource_pool: Single<&mut ResourcePool<Fire>, With<Player>>,
-) {
-    const FIRE_BREATH_CONSUMPTION_RATIO: i16 = 1;
-    fire_resource_pool.subtract(FIRE_BREATH_CONSUMPTION_RATIO);
+/// The dragon grows as it flies higher, which the cone mustn't, so its reach stays the same
+/// as the bursts, fireballs and scorch marks that don't follow the dragon's scale.
+fn breath_cone_transform(growth: f32, dragon_scale: f32) -> Transform {
+    Transform::from_xyz(0., MOUTH_DISTANCE / dragon_scale, 0.)
+        .with_scale(Vec3::splat(growth / dragon_scale))
 }
 
-fn restore_fire_breath_resource(
-    player: Single<(&Actions<DefaultInputContext>, &mut ResourcePool<Fire>), With<Player>>,
+fn consume_fire_breath_resource(
+    mut fire_resource_pool: Single<&mut ResourcePool<Fire>, With<Player>>,
 ) {
-    const FIRE_BREATH_RESTORATION_RATIO: i16 = 1;
-
-    let (actions, mut fire_resource_pool) = player.into_inner();
-
-    if let Ok(fire_breath_action_state) = actions.state::<FireBreath>() {
-        if fire_breath_action_state == ActionState::Fired && fire_resource_pool.is_empty() {
-            return;
-        }
-
-        if fire_breath_action_state != ActionState::Fired {
-            fire_resource_pool.add(FIRE_BREATH_RESTORATION_RATIO);
-        }
-    }
+    fire_resource_pool.subtract(BreathAbility::Cone.cost());
 }
 
 fn on_fire_breath_started(
@@ -131,39 +190,104 @@ fn on_fire_breath_started(
 
 fn on_fire_breath_ongoing(
     _trigger: Trigger<Ongoing<FireBreath>>,
-    mut player_animation_indices: Single<&mut AnimationIndices, With<Player>>,
+    mut player_animation: Single<&mut SpriteAnimation, With<Player>>,
 ) {
-    **player_animation_indices = AnimationIndices::new(6, 8);
+    player_animation.play("inhale");
 }
 
 fn on_fire_breath_fired(
     trigger: Trigger<Fired<FireBreath>>,
     mut subtract_fire_resource_event_writer: EventWriter<SubtractFireResourceEvent>,
-    player: Single<(&mut AnimationIndices, &ResourcePool<Fire>), With<Player>>,
+    mut burst_event_writer: EventWriter<BreathBurst>,
+    player: Single<
+        (
+            &mut SpriteAnimation,
+            &ResourcePool<Fire>,
+            &Heat,
+            &SelectedBreath,
+        ),
+        With<Player>,
+    >,
     dragon_breath_audio_channel: Res<AudioChannel<DragonBreathChannel>>,
     asset_server: Res<AssetServer>,
 ) {
-    let (mut player_animation_indices, fire_resource_pool) = player.into_inner();
+    let (mut player_animation, fire_resource_pool, heat, selected_breath) = player.into_inner();
 
-    if !fire_resource_pool.is_empty() {
+    // An overheated dragon can only wait for its breath to cool down.
+    if heat.is_overheated() {
+        if trigger.fired_secs == 0.0 {
+            player_animation.play("fly");
+        }
+        return;
+    }
+
+    if trigger.fired_secs == 0.0 {
+        dragon_breath_audio_channel
+            .play(asset_server.load("sfx/breathstart.ogg"))
+            .with_playback_rate(CHARGED_SFX_PLAYBACK_RATE);
+    }
+
+    // The fireball keeps inhaling while it charges and pays for itself once it is let go.
+    if **selected_breath == BreathAbility::Fireball {
+        return;
+    }
+
+    // A fully charged cone opens with its strongest burst.
+    if trigger.fired_secs == 0.0 && **selected_breath == BreathAbility::Cone {
+        release_breath_burst(
+            &mut burst_event_writer,
+            &dragon_breath_audio_channel,
+            &asset_server,
+            1.,
+        );
+    }
+
+    if **selected_breath == BreathAbility::Cone && !fire_resource_pool.is_empty() {
         subtract_fire_resource_event_writer.write(SubtractFireResourceEvent);
     }
 
     if trigger.fired_secs == 0.0 {
-        *player_animation_indices = Player::default_animation_indices();
+        player_animation.play("fly");
         dragon_breath_audio_channel
             .play(asset_server.load("sfx/breathloop.ogg"))
             .looped();
     }
 }
 
+/// Letting go before the breath starts releases whatever heat the cone stored so far.
 fn on_fire_breath_canceled(
-    _trigger: Trigger<Canceled<FireBreath>>,
-    mut player_animation_indices: Single<&mut AnimationIndices, With<Player>>,
+    trigger: Trigger<Canceled<FireBreath>>,
+    mut burst_event_writer: EventWriter<BreathBurst>,
+    player: Single<(&mut SpriteAnimation, &SelectedBreath), With<Player>>,
     dragon_breath_audio_channel: Res<AudioChannel<DragonBreathChannel>>,
+    asset_server: Res<AssetServer>,
 ) {
-    **player_animation_indices = Player::default_animation_indices();
+    let (mut player_animation, selected_breath) = player.into_inner();
+
+    player_animation.play("fly");
     dragon_breath_audio_channel.stop();
+
+    let charge = breath_charge(trigger.elapsed_secs);
+    if **selected_breath == BreathAbility::Cone && charge >= MIN_RELEASE_CHARGE {
+        release_breath_burst(
+            &mut burst_event_writer,
+            &dragon_breath_audio_channel,
+            &asset_server,
+            charge,
+        );
+    }
+}
+
+fn release_breath_burst(
+    burst_event_writer: &mut EventWriter<BreathBurst>,
+    dragon_breath_audio_channel: &AudioChannel<DragonBreathChannel>,
+    asset_server: &AssetServer,
+    charge: f32,
+) {
+    burst_event_writer.write(BreathBurst::new(charge));
+    dragon_breath_audio_channel
+        .play(asset_server.load("sfx/breathend.ogg"))
+        .with_playback_rate(RELEASE_SFX_PLAYBACK_RATE);
 }
 
 fn on_fire_breath_completed(
@@ -179,6 +303,16 @@ fn on_fire_breath_completed(
     }
 }
 
+fn remove_breath_cone(
+    _trigger: Trigger<Completed<FireBreath>>,
+    mut commands: Commands,
+    cone_query: Query<Entity, With<BreathCone>>,
+) {
+    for cone in &cone_query {
+        commands.entity(cone).despawn();
+    }
+}
+
 fn play_breath_end_sfx(
     fire_resource_pool: Single<&ResourcePool<Fire>, (With<Player>, Changed<ResourcePool<Fire>>)>,
     audio: Res<Audio>,
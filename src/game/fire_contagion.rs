@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::playing;
+
+use super::{
+    combat::OnFire,
+    score_system::{ScoreEvent, ScoreEventType},
+    spatial_index::{SpatialIndex, SpatialLayer},
+    TILE_SIZE,
+};
+
+/// How close a burning unit has to get to set something else alight.
+const CONTAGION_RADIUS: f32 = TILE_SIZE.x;
+/// Points for each link of a chain reaction, multiplied by how far down the chain the fire is.
+const CHAIN_REACTION_POINTS: i32 = 15;
+
+pub(super) struct FireContagionPlugin;
+
+impl Plugin for FireContagionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, spread_fire.run_if(playing()));
+    }
+}
+
+/// Lets an entity catch fire from burning units that touch it.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Flammable {
+    /// Chance of catching fire each time a different burning unit touches it.
+    pub ignition_chance: f32,
+}
+
+impl Flammable {
+    pub fn new(ignition_chance: f32) -> Self {
+        Self { ignition_chance }
+    }
+}
+
+/// Tracks where a fire came from and who it already tried to spread to.
+#[derive(Component, Default)]
+pub struct FireSpread {
+    /// Number of contagions between this fire and the kill that started it.
+    chain: u32,
+    attempted: Vec<Entity>,
+}
+
+impl FireSpread {
+    fn caught_from(&self) -> Self {
+        Self {
+            chain: self.chain + 1,
+            attempted: Vec::new(),
+        }
+    }
+}
+
+fn spread_fire(
+    mut commands: Commands,
+    mut score_event_writer: EventWriter<ScoreEvent>,
+    mut burning_query: Query<(Entity, &Transform, &mut FireSpread), With<OnFire>>,
+    flammable_query: Query<&Flammable, Without<OnFire>>,
+    spatial_index: Res<SpatialIndex>,
+    mut ignited: Local<HashSet<Entity>>,
+) {
+    let mut rng = rand::rng();
+    ignited.clear();
+
+    for (burning_entity, transform, mut fire_spread) in &mut burning_query {
+        let position = transform.translation.xy();
+        let neighbors = spatial_index
+            .query_radius(SpatialLayer::Enemies, position, CONTAGION_RADIUS)
+            .chain(spatial_index.query_radius(SpatialLayer::Buildings, position, CONTAGION_RADIUS));
+
+        for (neighbor, _) in neighbors {
+            if neighbor == burning_entity
+                || ignited.contains(&neighbor)
+                || fire_spread.attempted.contains(&neighbor)
+            {
+                continue;
+            }
+
+            let Ok(flammable) = flammable_query.get(neighbor) else {
+                continue;
+            };
+
+            fire_spread.attempted.push(neighbor);
+
+            if !rng.random_bool(flammable.ignition_chance.clamp(0., 1.) as f64) {
+                continue;
+            }
+
+            let caught_fire_spread = fire_spread.caught_from();
+            score_event_writer.write(ScoreEvent::new(
+                CHAIN_REACTION_POINTS * caught_fire_spread.chain as i32,
+                ScoreEventType::AddPoints,
+            ));
+            commands
+                .entity(neighbor)
+                .try_insert((OnFire, caught_fire_spread));
+            ignited.insert(neighbor);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_enhanced_input::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    animation::SpriteAnimation,
+    camera::{RenderLayer, YSorted},
+    input::actions::FireBreath,
+    playing, AppState,
+};
+
+use super::{
+    altitude::Altitude,
+    breath_ability::{BreathAbility, BreathCooldowns, BreathEffectAssets, SelectedBreath},
+    combat::DamagePerSecond,
+    damage::DamageType,
+    game_timer::GameTimer,
+    heat::Heat,
+    resource_pool::{Fire, ResourcePool},
+    Player, BARRIER_GROUP, BUILDING_GROUP, ENEMY_GROUP, FIRE_BREATH_GROUP, PROJECTILE_GROUP,
+    TILE_SIZE,
+};
+
+const FIRE_WALL_SECONDS: f32 = 4.;
+const FIRE_WALL_DAMAGE_PER_SECOND: f32 = 30.;
+/// Distance the dragon flies between two segments of the wall.
+const SEGMENT_SPACING: f32 = TILE_SIZE.x;
+/// How far behind the dragon the wall is laid.
+const SEGMENT_OFFSET: f32 = 40.;
+
+pub(super) struct FireWallPlugin;
+
+impl Plugin for FireWallPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, expire_fire_wall_segments.run_if(playing()));
+
+        app.add_observer(lay_fire_wall);
+        app.add_observer(finish_fire_wall);
+    }
+}
+
+/// One piece of burning ground, damaging whatever stands in it until it dies down.
+#[derive(Component)]
+#[require(
+    Sensor,
+    CollisionGroups::new(
+        FIRE_BREATH_GROUP,
+        BARRIER_GROUP | BUILDING_GROUP | ENEMY_GROUP | PROJECTILE_GROUP,
+    ),
+    DamagePerSecond::new(FIRE_WALL_DAMAGE_PER_SECOND, DamageType::Fire),
+    GameTimer::<FireWallSegment>::from_seconds_once(FIRE_WALL_SECONDS),
+    RenderLayers::layer(RenderLayer::Ground.into()),
+    StateScoped::<AppState>(AppState::GameOver),
+)]
+pub struct FireWallSegment;
+
+/// Where the wall being laid got its last segment.
+#[derive(Component)]
+pub struct FireWallTrail(Vec2);
+
+fn lay_fire_wall(
+    _trigger: Trigger<Fired<FireBreath>>,
+    mut commands: Commands,
+    player: Single<
+        (
+            Entity,
+            &Transform,
+            &SelectedBreath,
+            &BreathCooldowns,
+            &mut ResourcePool<Fire>,
+            &Heat,
+            &Altitude,
+            Option<&FireWallTrail>,
+        ),
+        With<Player>,
+    >,
+    breath_effect_assets: Res<BreathEffectAssets>,
+) {
+    let (
+        player_entity,
+        player_transform,
+        selected_breath,
+        cooldowns,
+        mut fire_resource_pool,
+        heat,
+        altitude,
+        trail,
+    ) = player.into_inner();
+
+    if **selected_breath != BreathAbility::FireWall || !cooldowns.is_ready(BreathAbility::FireWall)
+    {
+        return;
+    }
+
+    let direction = player_transform.rotation.mul_vec3(Vec3::Y).xy();
+    let position = player_transform.translation.xy() - direction * SEGMENT_OFFSET;
+
+    if trail.is_some_and(|trail| trail.0.distance(position) < SEGMENT_SPACING)
+        || fire_resource_pool.current() < BreathAbility::FireWall.cost()
+        || heat.is_overheated()
+    {
+        return;
+    }
+
+    fire_resource_pool.subtract(BreathAbility::FireWall.cost());
+    commands
+        .entity(player_entity)
+        .insert(FireWallTrail(position));
+    commands.spawn((
+        FireWallSegment,
+        Fire,
+        // Laid from high up, the wall burns weaker for as long as it lasts.
+        DamagePerSecond::new(
+            FIRE_WALL_DAMAGE_PER_SECOND * altitude.breath_effectiveness(),
+            DamageType::Fire,
+        ),
+        BreathAbility::FireWall.collider(),
+        breath_effect_assets.fire_sprite(),
+        SpriteAnimation::new(breath_effect_assets.fire_animation.clone(), "burn"),
+        Transform::from_translation(position.extend(5.)).with_rotation(player_transform.rotation),
+        YSorted,
+    ));
+}
+
+/// The cooldown starts once the wall is finished, and only if any of it was laid.
+fn finish_fire_wall(
+    _trigger: Trigger<Completed<FireBreath>>,
+    mut commands: Commands,
+    player: Single<(Entity, &mut BreathCooldowns), (With<Player>, With<FireWallTrail>)>,
+) {
+    let (player_entity, mut cooldowns) = player.into_inner();
+
+    cooldowns.start(BreathAbility::FireWall);
+    commands.entity(player_entity).remove::<FireWallTrail>();
+}
+
+fn expire_fire_wall_segments(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut GameTimer<FireWallSegment>)>,
+    time: Res<Time>,
+) {
+    for (entity, mut timer) in &mut query {
+        if timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).try_despawn();
+        }
+    }
+}
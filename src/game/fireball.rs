@@ -0,0 +1,235 @@
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_enhanced_input::prelude::*;
+use bevy_enoki::prelude::*;
+use bevy_rapier2d::{plugin::ReadRapierContext, prelude::*};
+
+use crate::{
+    animation::SpriteAnimation,
+    camera::{CameraShakeEvent, RenderLayer, YSorted},
+    input::actions::FireBreath,
+    playing, AppState,
+};
+
+use super::{
+    altitude::Altitude,
+    breath_ability::{
+        BreathAbility, BreathCooldowns, BreathEffectAssets, SelectedBreath, MOUTH_DISTANCE,
+    },
+    breath_charge::{breath_charge, MIN_RELEASE_CHARGE},
+    damage::{Damage, DamageEvent, DamageType},
+    game_timer::GameTimer,
+    heat::Heat,
+    resource_pool::{Fire, ResourcePool},
+    spatial_index::{SpatialIndex, SpatialLayer},
+    Player, BARRIER_GROUP, BUILDING_GROUP, ENEMY_GROUP, FIRE_BREATH_GROUP, TILE_SIZE,
+};
+
+const FIREBALL_SPEED: f32 = 500.;
+const FIREBALL_LIFETIME: f32 = 1.2;
+const MIN_DAMAGE: f32 = 20.;
+const MAX_DAMAGE: f32 = 60.;
+const MIN_EXPLOSION_RADIUS: f32 = TILE_SIZE.x * 2.;
+const MAX_EXPLOSION_RADIUS: f32 = TILE_SIZE.x * 4.;
+
+pub(super) struct FireballPlugin;
+
+impl Plugin for FireballPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (move_fireballs, explode_fireballs)
+                .chain()
+                .run_if(playing()),
+        );
+
+        app.add_observer(launch_charged_fireball);
+        app.add_observer(launch_fireball_early);
+    }
+}
+
+/// Flies straight ahead and explodes on the first thing it touches, or once it runs out of time.
+#[derive(Component)]
+#[require(
+    Sensor,
+    CollisionGroups::new(FIRE_BREATH_GROUP, BARRIER_GROUP | BUILDING_GROUP | ENEMY_GROUP),
+    GameTimer::<Fireball>::from_seconds_once(FIREBALL_LIFETIME),
+    RenderLayers::layer(RenderLayer::Sky.into()),
+    StateScoped::<AppState>(AppState::GameOver),
+)]
+pub struct Fireball {
+    source: Entity,
+    velocity: Vec2,
+    damage: Damage,
+    explosion_radius: f32,
+}
+
+impl Fireball {
+    pub fn explosion_radius(&self) -> f32 {
+        self.explosion_radius
+    }
+}
+
+type FireballLauncher<'a> = (
+    Entity,
+    &'a Transform,
+    &'a SelectedBreath,
+    &'a mut BreathCooldowns,
+    &'a mut ResourcePool<Fire>,
+    &'a Heat,
+    &'a Altitude,
+    &'a mut SpriteAnimation,
+);
+
+fn launch_charged_fireball(
+    trigger: Trigger<Completed<FireBreath>>,
+    mut commands: Commands,
+    player: Single<FireballLauncher, With<Player>>,
+    breath_effect_assets: Res<BreathEffectAssets>,
+) {
+    launch_fireball(
+        &mut commands,
+        player.into_inner(),
+        &breath_effect_assets,
+        breath_charge(trigger.elapsed_secs),
+    );
+}
+
+/// Letting go before the breath is fully charged still throws a smaller fireball.
+fn launch_fireball_early(
+    trigger: Trigger<Canceled<FireBreath>>,
+    mut commands: Commands,
+    player: Single<FireballLauncher, With<Player>>,
+    breath_effect_assets: Res<BreathEffectAssets>,
+) {
+    launch_fireball(
+        &mut commands,
+        player.into_inner(),
+        &breath_effect_assets,
+        breath_charge(trigger.elapsed_secs),
+    );
+}
+
+fn launch_fireball(
+    commands: &mut Commands,
+    (
+        player_entity,
+        player_transform,
+        selected_breath,
+        mut cooldowns,
+        mut fire_resource_pool,
+        heat,
+        altitude,
+        mut animation,
+    ): (
+        Entity,
+        &Transform,
+        &SelectedBreath,
+        Mut<BreathCooldowns>,
+        Mut<ResourcePool<Fire>>,
+        &Heat,
+        &Altitude,
+        Mut<SpriteAnimation>,
+    ),
+    breath_effect_assets: &BreathEffectAssets,
+    charge: f32,
+) {
+    if **selected_breath != BreathAbility::Fireball {
+        return;
+    }
+
+    animation.play("fly");
+
+    if charge < MIN_RELEASE_CHARGE
+        || heat.is_overheated()
+        || !cooldowns.is_ready(BreathAbility::Fireball)
+        || fire_resource_pool.current() < BreathAbility::Fireball.cost()
+    {
+        return;
+    }
+
+    fire_resource_pool.subtract(BreathAbility::Fireball.cost());
+    cooldowns.start(BreathAbility::Fireball);
+
+    let direction = player_transform.rotation.mul_vec3(Vec3::Y).xy();
+    let position = player_transform.translation.xy() + direction * MOUTH_DISTANCE;
+
+    commands.spawn((
+        Fireball {
+            source: player_entity,
+            velocity: direction * FIREBALL_SPEED,
+            damage: Damage::new(
+                (MIN_DAMAGE.lerp(MAX_DAMAGE, charge) * altitude.breath_effectiveness()) as i16,
+                DamageType::Fire,
+            ),
+            explosion_radius: MIN_EXPLOSION_RADIUS.lerp(MAX_EXPLOSION_RADIUS, charge),
+        },
+        Fire,
+        BreathAbility::Fireball.collider(),
+        breath_effect_assets.fire_sprite(),
+        SpriteAnimation::new(breath_effect_assets.fire_animation.clone(), "burn"),
+        Transform::from_translation(position.extend(10.)).with_scale(Vec3::splat(1. + charge)),
+        YSorted,
+    ));
+}
+
+fn move_fireballs(
+    mut query: Query<(&mut Transform, &Fireball, &mut GameTimer<Fireball>)>,
+    time: Res<Time>,
+) {
+    for (mut transform, fireball, mut timer) in &mut query {
+        transform.translation += (fireball.velocity * time.delta_secs()).extend(0.);
+        timer.tick(time.delta());
+    }
+}
+
+fn explode_fireballs(
+    mut commands: Commands,
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut camera_shake_event_writer: EventWriter<CameraShakeEvent>,
+    query: Query<(Entity, &Transform, &Fireball, &GameTimer<Fireball>)>,
+    spatial_index: Res<SpatialIndex>,
+    rapier_context: ReadRapierContext,
+    breath_effect_assets: Res<BreathEffectAssets>,
+) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+
+    for (entity, transform, fireball, timer) in &query {
+        let hit_something = rapier_context
+            .intersection_pairs_with(entity)
+            .any(|(_, _, intersecting)| intersecting);
+
+        if !hit_something && !timer.finished() {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+
+        for layer in [SpatialLayer::Enemies, SpatialLayer::Buildings] {
+            for (target, _) in
+                spatial_index.query_radius(layer, position, fireball.explosion_radius)
+            {
+                damage_event_writer.write(DamageEvent::new(
+                    fireball.source,
+                    target,
+                    fireball.damage,
+                    position,
+                ));
+            }
+        }
+
+        camera_shake_event_writer.write(CameraShakeEvent::new(0.3));
+        commands.spawn((
+            ParticleSpawner::default(),
+            ParticleEffectHandle(breath_effect_assets.explosion_effect.clone()),
+            OneShot::Despawn,
+            RenderLayers::layer(RenderLayer::Ground.into()),
+            Transform::from_translation(position.extend(10.)).with_scale(Vec3::splat(
+                fireball.explosion_radius / MIN_EXPLOSION_RADIUS,
+            )),
+            StateScoped(AppState::GameOver),
+        ));
+        commands.entity(entity).try_despawn();
+    }
+}
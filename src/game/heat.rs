@@ -0,0 +1,238 @@
+use std::fmt;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+use bevy_kira_audio::{Audio, AudioChannel, AudioControl};
+use serde::Deserialize;
+
+use crate::{
+    audio::DragonBreathChannel,
+    input::{actions::FireBreath, DefaultInputContext},
+    playing,
+};
+
+use super::{
+    resource_pool::{Fire, ResourcePool},
+    status_effect::StatusModifiers,
+    Player,
+};
+
+pub(super) struct HeatPlugin;
+
+impl Plugin for HeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<HeatCurves>();
+        app.init_asset_loader::<HeatCurvesLoader>();
+
+        app.add_event::<Overheated>();
+
+        app.add_systems(Startup, load_heat_curves);
+        app.add_systems(
+            FixedUpdate,
+            (
+                update_heat,
+                play_overheat_sfx.run_if(on_event::<Overheated>),
+            )
+                .chain()
+                .run_if(playing()),
+        );
+    }
+}
+
+/// How breathing heats the dragon up and how its fire comes back, loaded from a `.heat.ron`
+/// file.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct HeatCurves {
+    /// Heat at which the dragon overheats.
+    max_heat: f32,
+    /// Heat built for each point of fire spent on any breath.
+    heat_per_fire: f32,
+    /// Heat lost each second while not breathing.
+    dissipation_per_second: f32,
+    /// Seconds an overheated dragon can't breathe, cooling down completely meanwhile.
+    overheat_seconds: f32,
+    regeneration: Regeneration,
+}
+
+/// Fire comes back slowly at first and faster the longer the dragon doesn't breathe.
+#[derive(Deserialize)]
+struct Regeneration {
+    /// Seconds after the breath stops before any fire comes back.
+    delay: f32,
+    /// Fire regained each second once the delay is over.
+    start_per_second: f32,
+    /// Fire per second added to the rate for each second spent regenerating.
+    acceleration: f32,
+    max_per_second: f32,
+}
+
+impl Regeneration {
+    fn per_second(&self, seconds_regenerating: f32) -> f32 {
+        (self.start_per_second + self.acceleration * seconds_regenerating).min(self.max_per_second)
+    }
+}
+
+#[derive(Resource)]
+struct HeatCurvesHandle(Handle<HeatCurves>);
+
+/// Sent when the dragon gets too hot to keep breathing.
+#[derive(Event)]
+pub struct Overheated;
+
+/// Heat built up by breathing fire, and how the dragon's fire comes back.
+#[derive(Component, Default)]
+pub struct Heat {
+    current: f32,
+    /// Forced cooldown, running while the dragon is overheated.
+    overheat: Option<Timer>,
+    /// Seconds since the dragon last breathed or cooled down from overheating.
+    seconds_since_breath: f32,
+    /// Fire regenerated that doesn't add up to a whole point yet.
+    regeneration_remainder: f32,
+    /// Fire in the pool at the end of the last tick, to tell how much was spent since.
+    last_fire: Option<i16>,
+}
+
+impl Heat {
+    pub fn is_overheated(&self) -> bool {
+        self.overheat.is_some()
+    }
+}
+
+fn load_heat_curves(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(HeatCurvesHandle(
+        asset_server.load("config/dragon.heat.ron"),
+    ));
+}
+
+/// Every ability pays for itself from the fire pool, so whatever left the pool since the
+/// last tick is what heats the dragon up.
+fn update_heat(
+    mut overheated_event_writer: EventWriter<Overheated>,
+    player: Single<
+        (
+            &Actions<DefaultInputContext>,
+            &mut Heat,
+            &mut ResourcePool<Fire>,
+            Option<&StatusModifiers>,
+        ),
+        With<Player>,
+    >,
+    handle: Res<HeatCurvesHandle>,
+    heat_curves: Res<Assets<HeatCurves>>,
+    time: Res<Time>,
+) {
+    let Some(curves) = heat_curves.get(&handle.0) else {
+        return;
+    };
+
+    let (actions, mut heat, mut fire_resource_pool, modifiers) = player.into_inner();
+    let modifiers = modifiers.copied().unwrap_or_default();
+    let breathing = actions
+        .state::<FireBreath>()
+        .is_ok_and(|state| state == ActionState::Fired);
+
+    let spent = heat.last_fire.map_or(0, |last_fire| {
+        (last_fire - fire_resource_pool.current()).max(0)
+    });
+    heat.current += spent as f32 * curves.heat_per_fire * modifiers.heat_built();
+
+    if let Some(overheat) = heat.overheat.as_mut() {
+        overheat.tick(time.delta());
+        let remaining = overheat.fraction_remaining();
+        let cooled_down = overheat.finished();
+
+        heat.current = curves.max_heat * remaining;
+        heat.seconds_since_breath = 0.;
+        if cooled_down {
+            heat.overheat = None;
+        }
+    } else if heat.current >= curves.max_heat {
+        heat.current = curves.max_heat;
+        heat.overheat = Some(Timer::from_seconds(
+            curves.overheat_seconds,
+            TimerMode::Once,
+        ));
+        heat.seconds_since_breath = 0.;
+        overheated_event_writer.write(Overheated);
+    } else if breathing {
+        heat.seconds_since_breath = 0.;
+    } else {
+        heat.current = (heat.current - curves.dissipation_per_second * time.delta_secs()).max(0.);
+        heat.seconds_since_breath += time.delta_secs();
+    }
+
+    let seconds_regenerating = heat.seconds_since_breath - curves.regeneration.delay;
+    if seconds_regenerating > 0. {
+        heat.regeneration_remainder += curves.regeneration.per_second(seconds_regenerating)
+            * modifiers.fire_regeneration()
+            * time.delta_secs();
+
+        let regenerated = heat.regeneration_remainder.trunc();
+        heat.regeneration_remainder -= regenerated;
+        if regenerated > 0. {
+            fire_resource_pool.add(regenerated as i16);
+        }
+    } else {
+        heat.regeneration_remainder = 0.;
+    }
+
+    heat.last_fire = Some(fire_resource_pool.current());
+}
+
+fn play_overheat_sfx(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    dragon_breath_audio_channel: Res<AudioChannel<DragonBreathChannel>>,
+) {
+    audio.play(asset_server.load("sfx/breathend.ogg"));
+    dragon_breath_audio_channel.stop();
+}
+
+#[derive(Default)]
+struct HeatCurvesLoader;
+
+#[derive(Debug)]
+enum HeatCurvesLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for HeatCurvesLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read heat curves: {error}"),
+            Self::Ron(error) => write!(f, "could not parse heat curves: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for HeatCurvesLoaderError {}
+
+impl AssetLoader for HeatCurvesLoader {
+    type Asset = HeatCurves;
+    type Settings = ();
+    type Error = HeatCurvesLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(HeatCurvesLoaderError::Io)?;
+
+        ron::de::from_bytes(&bytes).map_err(HeatCurvesLoaderError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["heat.ron"]
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{
+    color::palettes::css::{BLACK, GOLD, LIGHT_GRAY, ORCHID, RED, TAN},
+    prelude::*,
+    render::view::RenderLayers,
+    sprite::Anchor,
+};
+
+use crate::{camera::RenderLayer, playing, AppState};
+
+use super::{
+    damage::{DamageDealt, DamageType},
+    enemy::Behavior,
+    game_timer::GameTimer,
+    resource_pool::{Health, ResourcePool},
+    Player, TILE_SIZE,
+};
+
+const HIT_FLASH_COLOR: Color = Color::srgb(1.0, 0.35, 0.35);
+const HIT_FLASH_SECONDS: f32 = 0.15;
+const KNOCKBACK_SPEED: f32 = 160.;
+const DAMAGE_NUMBER_BATCH_SECONDS: f32 = 0.25;
+const DAMAGE_NUMBER_LIFETIME_SECONDS: f32 = 0.8;
+const DAMAGE_NUMBER_RISE_SPEED: f32 = 40.;
+const HEALTH_BAR_SIZE: Vec2 = Vec2::new(TILE_SIZE.x * 2., 3.);
+const HEALTH_BAR_OFFSET: Vec3 = Vec3::new(0., TILE_SIZE.y * 2., 0.1);
+
+pub(super) struct HitFeedbackPlugin;
+
+impl Plugin for HitFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OverheadHealthBars>();
+
+        app.add_systems(
+            FixedUpdate,
+            (
+                handle_hit_events,
+                update_hit_flashes,
+                apply_knockback,
+                spawn_damage_numbers,
+                update_damage_numbers,
+                update_overhead_health_bars,
+            )
+                .run_if(playing()),
+        );
+    }
+}
+
+/// Whether damaged units and buildings display a health bar above them.
+#[derive(Resource)]
+pub struct OverheadHealthBars(pub bool);
+
+impl Default for OverheadHealthBars {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Tints the sprite while active, holding the color to restore afterwards.
+/// New hits don't knock back an entity that is still flashing.
+#[derive(Component)]
+#[require(GameTimer::<HitFlash>::from_seconds_once(HIT_FLASH_SECONDS))]
+pub struct HitFlash(Color);
+
+/// Velocity pushing an entity away from the source of a hit, decaying to zero.
+#[derive(Component, Deref, DerefMut)]
+pub struct Knockback(Vec2);
+
+impl Knockback {
+    pub fn new(velocity: Vec2) -> Self {
+        Self(velocity)
+    }
+}
+
+/// Damage taken during the current batch window, shown as a single number once it closes.
+/// The number takes the color of the last damage type dealt.
+#[derive(Component)]
+#[require(GameTimer::<PendingDamageNumber>::from_seconds_once(DAMAGE_NUMBER_BATCH_SECONDS))]
+pub struct PendingDamageNumber(i16, DamageType);
+
+#[derive(Component)]
+#[require(GameTimer::<DamageNumber>::from_seconds_once(DAMAGE_NUMBER_LIFETIME_SECONDS))]
+pub struct DamageNumber;
+
+#[derive(Component)]
+pub struct OverheadHealthBar;
+
+#[derive(Component)]
+pub struct OverheadHealthBarFill;
+
+/// Components inserted here only show up once the commands apply, so several hits on the same
+/// target within a tick are tracked locally instead.
+fn handle_hit_events(
+    mut commands: Commands,
+    mut damage_dealt_event_reader: EventReader<DamageDealt>,
+    // The player gets its own feedback from the HUD.
+    mut target_query: Query<
+        (
+            &Transform,
+            &ResourcePool<Health>,
+            &mut Sprite,
+            Has<HitFlash>,
+            Has<Behavior>,
+            Option<&mut PendingDamageNumber>,
+            Has<OverheadHealthBar>,
+            Option<&Knockback>,
+        ),
+        Without<Player>,
+    >,
+    overhead_health_bars: Res<OverheadHealthBars>,
+) {
+    let mut new_damage_numbers = HashMap::<Entity, PendingDamageNumber>::new();
+    let mut hit_this_tick = HashSet::<Entity>::new();
+
+    for &DamageDealt {
+        source,
+        target,
+        damage_type,
+        amount,
+        source_position,
+    } in damage_dealt_event_reader.read()
+    {
+        let Ok((
+            transform,
+            health,
+            mut sprite,
+            is_flashing,
+            is_unit,
+            pending_damage_number,
+            has_health_bar,
+            knockback,
+        )) = target_query.get_mut(target)
+        else {
+            continue;
+        };
+
+        match pending_damage_number {
+            Some(mut pending_damage_number) => {
+                pending_damage_number.0 += amount;
+                pending_damage_number.1 = damage_type;
+            }
+            None => {
+                let pending_damage_number = new_damage_numbers
+                    .entry(target)
+                    .or_insert(PendingDamageNumber(0, damage_type));
+                pending_damage_number.0 += amount;
+                pending_damage_number.1 = damage_type;
+            }
+        }
+
+        // Entities at zero health may be despawned before these commands apply.
+        if health.is_empty() {
+            continue;
+        }
+
+        let first_hit_this_tick = hit_this_tick.insert(target);
+
+        if !has_health_bar && first_hit_this_tick && overhead_health_bars.0 {
+            commands
+                .entity(target)
+                .insert(OverheadHealthBar)
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            Sprite::from_color(BLACK, HEALTH_BAR_SIZE),
+                            Transform::from_translation(HEALTH_BAR_OFFSET),
+                            RenderLayers::layer(RenderLayer::Ground.into()),
+                        ))
+                        .with_child((
+                            OverheadHealthBarFill,
+                            Sprite {
+                                anchor: Anchor::CenterLeft,
+                                ..Sprite::from_color(RED, HEALTH_BAR_SIZE)
+                            },
+                            Transform::from_xyz(-HEALTH_BAR_SIZE.x / 2., 0., 0.1),
+                            RenderLayers::layer(RenderLayer::Ground.into()),
+                        ));
+                });
+        }
+
+        if is_flashing || !first_hit_this_tick {
+            continue;
+        }
+
+        commands.entity(target).insert(HitFlash(sprite.color));
+        sprite.color = HIT_FLASH_COLOR;
+
+        // Damage an entity deals itself, like a fire with nobody to blame, doesn't push it.
+        // A harder shove, like a dive landing or a tail swipe, is left to play out.
+        if is_unit
+            && source != target
+            && knockback.is_none_or(|knockback| knockback.length() < KNOCKBACK_SPEED)
+        {
+            let direction = (transform.translation.xy() - source_position).normalize_or_zero();
+            commands
+                .entity(target)
+                .insert(Knockback(direction * KNOCKBACK_SPEED));
+        }
+    }
+
+    for (target, pending_damage_number) in new_damage_numbers {
+        commands.entity(target).try_insert(pending_damage_number);
+    }
+}
+
+fn update_hit_flashes(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Sprite, &HitFlash, &mut GameTimer<HitFlash>)>,
+    time: Res<Time>,
+) {
+    for (entity, mut sprite, hit_flash, mut timer) in &mut query {
+        if timer.tick(time.delta()).just_finished() {
+            sprite.color = hit_flash.0;
+            commands
+                .entity(entity)
+                .remove::<(HitFlash, GameTimer<HitFlash>)>();
+        }
+    }
+}
+
+fn apply_knockback(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut Knockback)>,
+    time: Res<Time>,
+) {
+    const KNOCKBACK_DECAY: f32 = 10.;
+
+    let delta_secs = time.delta_secs();
+
+    for (entity, mut transform, mut knockback) in &mut query {
+        transform.translation += (**knockback * delta_secs).extend(0.);
+        **knockback *= (1. - KNOCKBACK_DECAY * delta_secs).max(0.);
+
+        if knockback.length() < 1. {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
+fn spawn_damage_numbers(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &PendingDamageNumber,
+        &mut GameTimer<PendingDamageNumber>,
+    )>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    for (entity, transform, pending_damage_number, mut timer) in &mut query {
+        if timer.tick(time.delta()).just_finished() {
+            commands.spawn((
+                DamageNumber,
+                Text2d::new(pending_damage_number.0.to_string()),
+                TextFont::from_font(asset_server.load("fonts/Prince Valiant.ttf"))
+                    .with_font_size(20.0),
+                TextColor(damage_number_color(pending_damage_number.1)),
+                Transform::from_translation(
+                    transform.translation.xy().extend(0.) + HEALTH_BAR_OFFSET,
+                ),
+                RenderLayers::layer(RenderLayer::Ui.into()),
+                StateScoped(AppState::GameOver),
+            ));
+            commands
+                .entity(entity)
+                .remove::<(PendingDamageNumber, GameTimer<PendingDamageNumber>)>();
+        }
+    }
+}
+
+fn damage_number_color(damage_type: DamageType) -> Color {
+    match damage_type {
+        DamageType::Fire => GOLD,
+        DamageType::Piercing => LIGHT_GRAY,
+        DamageType::Blunt => TAN,
+        DamageType::Magic => ORCHID,
+    }
+    .into()
+}
+
+fn update_damage_numbers(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut TextColor,
+            &mut GameTimer<DamageNumber>,
+        ),
+        With<DamageNumber>,
+    >,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut text_color, mut timer) in &mut query {
+        timer.tick(time.delta());
+        transform.translation.y += DAMAGE_NUMBER_RISE_SPEED * time.delta_secs();
+        text_color.set_alpha(timer.fraction_remaining());
+
+        if timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_overhead_health_bars(
+    health_query: Query<
+        (Entity, &ResourcePool<Health>),
+        (With<OverheadHealthBar>, Changed<ResourcePool<Health>>),
+    >,
+    children_query: Query<&Children>,
+    mut fill_query: Query<&mut Transform, With<OverheadHealthBarFill>>,
+) {
+    for (entity, health) in &health_query {
+        for descendant in children_query.iter_descendants(entity) {
+            if let Ok(mut fill_transform) = fill_query.get_mut(descendant) {
+                fill_transform.scale.x = health.current_percentage();
+            }
+        }
+    }
+}
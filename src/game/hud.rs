@@ -1,11 +1,15 @@
 use bevy::{
-    color::palettes::css::{GOLD, LIMEGREEN, RED},
+    color::palettes::css::{GOLD, GRAY, LIMEGREEN, ORANGE, RED},
     prelude::*,
 };
 
 use crate::{playing, AppState};
 
 use super::{
+    breath_ability::SelectedBreath,
+    breath_charge::BreathCharge,
+    dragon_ability::{AbilityCooldowns, DragonAbility},
+    heat::Heat,
     resource_pool::{Fire, Health, ResourcePool},
     score_system::Score,
     Player,
@@ -26,7 +30,11 @@ impl Plugin for HudPlugin {
             (
                 update_health_bar_display,
                 update_fire_bar_display,
+                update_fire_bar_color,
+                update_breath_charge_display,
                 update_score_display,
+                update_breath_ability_display,
+                update_ability_cooldown_display,
             )
                 .run_if(playing()),
         );
@@ -39,9 +47,18 @@ struct HealthBar;
 #[derive(Component)]
 struct FireBreathBar;
 
+#[derive(Component)]
+struct BreathChargeBar;
+
 #[derive(Component)]
 struct ScoreDisplay;
 
+#[derive(Component)]
+struct BreathAbilityDisplay;
+
+#[derive(Component)]
+struct AbilityCooldownDisplay;
+
 fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn((
         StateScoped(AppState::GameOver),
@@ -73,6 +90,24 @@ fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
                     HealthBar,
                 )],
             ),
+            (
+                Node {
+                    border: UiRect::all(Val::Px(BAR_BORDER_SIZE)),
+                    width: Val::Px(BAR_WIDTH),
+                    height: Val::Px(BAR_HEIGHT),
+                    ..default()
+                },
+                BorderColor::from(Color::BLACK),
+                children![(
+                    Node {
+                        width: Val::Px(0.),
+                        height: Val::Px(BAR_HEIGHT - BAR_BORDER_SIZE * 2.),
+                        ..default()
+                    },
+                    BackgroundColor::from(ORANGE),
+                    BreathChargeBar,
+                )],
+            ),
             (
                 Node {
                     border: UiRect::all(Val::Px(BAR_BORDER_SIZE)),
@@ -109,6 +144,38 @@ fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
         TextColor(GOLD.into()),
         TextLayout::new_with_no_wrap(),
     ));
+
+    // Selected breath ability in the top left corner
+    commands.spawn((
+        StateScoped(AppState::GameOver),
+        BreathAbilityDisplay,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.),
+            top: Val::Px(16.),
+            ..default()
+        },
+        Text::new("Breath: Cone"),
+        TextFont::from_font(asset_server.load("fonts/Prince Valiant.ttf")).with_font_size(28.0),
+        TextColor(GOLD.into()),
+        TextLayout::new_with_no_wrap(),
+    ));
+
+    // Cooldowns of the other moves right below it
+    commands.spawn((
+        StateScoped(AppState::GameOver),
+        AbilityCooldownDisplay,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(16.),
+            top: Val::Px(48.),
+            ..default()
+        },
+        Text::new(""),
+        TextFont::from_font(asset_server.load("fonts/Prince Valiant.ttf")).with_font_size(22.0),
+        TextColor(GOLD.into()),
+        TextLayout::new_with_no_wrap(),
+    ));
 }
 
 fn update_health_bar_display(
@@ -125,6 +192,50 @@ fn update_fire_bar_display(
     fire_bar_node.width = Val::Px(BAR_WIDTH * player_fire.current_percentage());
 }
 
+/// The fire bar greys out while the dragon is too hot to breathe.
+fn update_fire_bar_color(
+    heat: Single<&Heat, (Changed<Heat>, With<Player>)>,
+    mut fire_bar_color: Single<&mut BackgroundColor, With<FireBreathBar>>,
+) {
+    let color = if heat.is_overheated() {
+        GRAY
+    } else {
+        LIMEGREEN
+    };
+    fire_bar_color.set_if_neq(BackgroundColor::from(color));
+}
+
+fn update_breath_charge_display(
+    charge: Single<&BreathCharge, (Changed<BreathCharge>, With<Player>)>,
+    mut charge_bar_node: Single<&mut Node, With<BreathChargeBar>>,
+) {
+    charge_bar_node.width = Val::Px(BAR_WIDTH * **charge.into_inner());
+}
+
+fn update_breath_ability_display(
+    selected_breath: Single<&SelectedBreath, (Changed<SelectedBreath>, With<Player>)>,
+    mut breath_ability_text: Single<&mut Text, With<BreathAbilityDisplay>>,
+) {
+    breath_ability_text.0 = format!("Breath: {}", selected_breath.label());
+}
+
+fn update_ability_cooldown_display(
+    cooldowns: Single<&AbilityCooldowns, (Changed<AbilityCooldowns>, With<Player>)>,
+    mut ability_cooldown_text: Single<&mut Text, With<AbilityCooldownDisplay>>,
+) {
+    let cooldowns = DragonAbility::ALL.map(|ability| {
+        let remaining_secs = cooldowns.remaining_secs(ability);
+
+        if remaining_secs > 0. {
+            format!("{}: {:.1}s", ability.label(), remaining_secs)
+        } else {
+            format!("{}: ready", ability.label())
+        }
+    });
+
+    ability_cooldown_text.0 = cooldowns.join("   ");
+}
+
 fn update_score_display(
     player_score: Single<&Score, (Changed<Score>, With<Player>)>,
     mut score_text: Single<&mut Text, With<ScoreDisplay>>,
@@ -15,7 +15,9 @@ use crate::{
 };
 
 use super::{
-    combat::{AttackDamage, AttackTimer, Range},
+    combat::{AttackDamage, AttackProjectile, AttackTimer, ProjectileKind, Range},
+    damage::{Damage, DamageType},
+    fire_contagion::Flammable,
     resource_pool::{Health, ResourcePool},
     Enemy,
 };
@@ -146,13 +148,16 @@ fn spawn_buildings(
         commands.spawn((
             Building,
             ActiveCollisionTypes::all(),
-            AttackDamage(5),
-            AttackTimer::new(4.),
+            (
+                AttackDamage(Damage::new(5, DamageType::Piercing)),
+                AttackProjectile(ProjectileKind::Arrow),
+                AttackTimer::new(4.),
+                Range(TILE_SIZE.x * 20.),
+            ),
             Collider::ball(HALF_TILE_SIZE.x),
             CollisionGroups::new(BUILDING_GROUP, ENEMY_GROUP | FIRE_BREATH_GROUP),
             ResourcePool::<Health>::new(1000),
             Enemy,
-            Range(TILE_SIZE.x * 20.),
             RenderLayers::layer(RenderLayer::Ground.into()),
             RigidBody::Fixed,
             Sprite {
@@ -327,7 +332,34 @@ pub struct TilesetObjectsTextureAtlasHandle(Handle<TextureAtlasLayout>);
 #[derive(Resource, Deref)]
 pub struct LevelMatrix(Matrix<Tile>);
 
+impl LevelMatrix {
+    pub fn tile_at(&self, position: Vec2) -> Option<Tile> {
+        let grid_position =
+            translate_transform_to_grid_space(&Transform::from_translation(position.extend(0.)));
+
+        self.0.get(grid_position).copied()
+    }
+
+    /// Finds the world position of the closest `tile` at most `max_distance` cells away.
+    pub fn nearest_tile(&self, position: Vec2, tile: Tile, max_distance: usize) -> Option<Vec2> {
+        let (center_x, center_y) =
+            translate_transform_to_grid_space(&Transform::from_translation(position.extend(0.)));
+        let min_x = center_x.saturating_sub(max_distance);
+        let min_y = center_y.saturating_sub(max_distance);
+
+        (min_y..=center_y + max_distance)
+            .flat_map(|y| (min_x..=center_x + max_distance).map(move |x| (x, y)))
+            .filter(|grid_position| self.0.get(*grid_position) == Some(&tile))
+            .map(|grid_position| translate_grid_position_to_world_space(&grid_position))
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+    }
+}
+
 #[derive(Component)]
+#[require(Flammable::new(0.25))]
 pub struct Building;
 
 #[derive(Component)]
@@ -0,0 +1,249 @@
+use bevy::{ecs::system::SystemParam, prelude::*, render::view::RenderLayers};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    audio::{PlaySoundEffectEvent, PlaybackSettings},
+    camera::RenderLayer,
+    playing, AppState,
+};
+
+use super::{
+    combat::{OnFire, Range, EXTINGUISHED_HEALTH_RATIO},
+    death::{Dying, Rescued},
+    game_timer::GameTimer,
+    resource_pool::{Health, ResourcePool},
+    spatial_index::{SpatialIndex, SpatialLayer},
+    Player, BARRIER_GROUP, FIRE_BREATH_GROUP, TILE_SIZE,
+};
+
+const HEAL_RADIUS: f32 = TILE_SIZE.x * 6.;
+const HEAL_AMOUNT: i16 = 5;
+const HEAL_COLOR: Color = Color::srgba(0.3, 1.0, 0.4, 0.3);
+const WATER_BARRIER_RADIUS: f32 = TILE_SIZE.x * 4.;
+const WATER_BARRIER_COLOR: Color = Color::srgba(0.2, 0.5, 1.0, 0.35);
+
+pub(super) struct MagePlugin;
+
+impl Plugin for MagePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MageEffectAssets>();
+
+        app.add_systems(
+            FixedUpdate,
+            (
+                cast_healing,
+                cast_water_barriers,
+                expire_water_barriers,
+                expand_heal_pulses,
+            )
+                .run_if(playing()),
+        );
+    }
+}
+
+/// Support caster that heals and extinguishes nearby allies and shields them with water.
+#[derive(Component)]
+#[require(
+    GameTimer::<Mage>::from_seconds(1.),
+    GameTimer::<WaterBarrierCooldown>::from_seconds(8.)
+)]
+pub struct Mage;
+
+struct WaterBarrierCooldown;
+
+/// Shelters whatever stands in or behind it from fire for as long as it lasts. Fireballs burst
+/// against it.
+#[derive(Component)]
+#[require(
+    ActiveCollisionTypes::all(),
+    Collider::ball(WATER_BARRIER_RADIUS),
+    CollisionGroups::new(BARRIER_GROUP, FIRE_BREATH_GROUP),
+    GameTimer::<WaterBarrier>::from_seconds_once(3.),
+    RenderLayers::layer(RenderLayer::Ground.into()),
+    Sensor,
+    StateScoped::<AppState>(AppState::GameOver),
+)]
+pub struct WaterBarrier;
+
+#[derive(Resource)]
+pub struct MageEffectAssets {
+    heal_pulse_mesh: Handle<Mesh>,
+    heal_pulse_material: Handle<ColorMaterial>,
+    water_barrier_mesh: Handle<Mesh>,
+    water_barrier_material: Handle<ColorMaterial>,
+}
+
+impl FromWorld for MageEffectAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let heal_pulse_mesh = meshes.add(Annulus::new(HEAL_RADIUS - 2., HEAL_RADIUS));
+        let water_barrier_mesh = meshes.add(Circle::new(WATER_BARRIER_RADIUS));
+
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        let heal_pulse_material = materials.add(HEAL_COLOR);
+        let water_barrier_material = materials.add(WATER_BARRIER_COLOR);
+
+        Self {
+            heal_pulse_mesh,
+            heal_pulse_material,
+            water_barrier_mesh,
+            water_barrier_material,
+        }
+    }
+}
+
+/// Water barriers standing between fire and what it would burn.
+#[derive(SystemParam)]
+pub struct WaterBarriers<'w, 's> {
+    barrier_query: Query<'w, 's, &'static Transform, With<WaterBarrier>>,
+}
+
+impl WaterBarriers<'_, '_> {
+    /// Whether fire coming from `source` is put out by a barrier before it reaches `target`.
+    pub fn shelter(&self, source: Vec2, target: Vec2) -> bool {
+        let path = target - source;
+
+        self.barrier_query.iter().any(|transform| {
+            let center = transform.translation.xy();
+            let along = ((center - source).dot(path) / path.length_squared().max(f32::EPSILON))
+                .clamp(0., 1.);
+
+            center.distance(source + path * along) <= WATER_BARRIER_RADIUS
+        })
+    }
+}
+
+/// Expanding ring drawn when a mage heals.
+#[derive(Component)]
+#[require(GameTimer::<HealPulse>::from_seconds_once(0.4))]
+pub struct HealPulse;
+
+fn cast_healing(
+    mut commands: Commands,
+    mut play_sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut mage_query: Query<(&Transform, &mut GameTimer<Mage>), With<Mage>>,
+    mut ally_query: Query<
+        (&mut ResourcePool<Health>, Has<OnFire>),
+        (Without<Player>, Without<Dying>),
+    >,
+    spatial_index: Res<SpatialIndex>,
+    mage_effect_assets: Res<MageEffectAssets>,
+    time: Res<Time>,
+) {
+    for (mage_transform, mut heal_timer) in &mut mage_query {
+        if !heal_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let mage_position = mage_transform.translation.xy();
+        let allies = spatial_index
+            .query_radius(SpatialLayer::Enemies, mage_position, HEAL_RADIUS)
+            .chain(spatial_index.query_radius(SpatialLayer::Buildings, mage_position, HEAL_RADIUS));
+
+        for (ally, _) in allies {
+            let Ok((mut health, is_on_fire)) = ally_query.get_mut(ally) else {
+                continue;
+            };
+
+            if is_on_fire {
+                let extinguished_health =
+                    ((health.max() as f32 * EXTINGUISHED_HEALTH_RATIO) as i16).max(1);
+
+                if health.is_empty() {
+                    commands.entity(ally).insert(Rescued);
+                }
+                health.set_current(extinguished_health);
+                commands.entity(ally).remove::<OnFire>();
+                play_sound_effect_event_writer.write(PlaySoundEffectEvent::new(
+                    "breathend.ogg",
+                    Some(PlaybackSettings {
+                        playback_rate: 2.2,
+                        volume: 0.4,
+                        ..default()
+                    }),
+                    None,
+                ));
+            } else if !health.is_empty() && health.current() < health.max() {
+                health.add(HEAL_AMOUNT);
+            }
+        }
+
+        commands.spawn((
+            HealPulse,
+            Mesh2d(mage_effect_assets.heal_pulse_mesh.clone()),
+            MeshMaterial2d(mage_effect_assets.heal_pulse_material.clone()),
+            Transform::from_translation(mage_position.extend(0.5)),
+            RenderLayers::layer(RenderLayer::Ground.into()),
+            StateScoped(AppState::GameOver),
+        ));
+    }
+}
+
+fn cast_water_barriers(
+    mut commands: Commands,
+    mut play_sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut mage_query: Query<(&Transform, &Range, &mut GameTimer<WaterBarrierCooldown>), With<Mage>>,
+    player_transform: Single<&Transform, With<Player>>,
+    mage_effect_assets: Res<MageEffectAssets>,
+    time: Res<Time>,
+) {
+    let player_position = player_transform.translation.xy();
+
+    for (mage_transform, range, mut cooldown_timer) in &mut mage_query {
+        if !cooldown_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let mage_position = mage_transform.translation.xy();
+
+        if mage_position.distance(player_position) > range.0 {
+            continue;
+        }
+
+        commands.spawn((
+            WaterBarrier,
+            Mesh2d(mage_effect_assets.water_barrier_mesh.clone()),
+            MeshMaterial2d(mage_effect_assets.water_barrier_material.clone()),
+            Transform::from_translation(mage_position.extend(0.5)),
+        ));
+
+        // A reversed, high pitched breath reads as water rushing in.
+        play_sound_effect_event_writer.write(PlaySoundEffectEvent::new(
+            "breathend.ogg",
+            Some(PlaybackSettings {
+                playback_rate: 1.8,
+                reverse: true,
+                volume: 0.5,
+                ..default()
+            }),
+            None,
+        ));
+    }
+}
+
+fn expire_water_barriers(
+    mut commands: Commands,
+    mut barrier_query: Query<(Entity, &mut GameTimer<WaterBarrier>), With<WaterBarrier>>,
+    time: Res<Time>,
+) {
+    for (entity, mut timer) in &mut barrier_query {
+        if timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn expand_heal_pulses(
+    mut commands: Commands,
+    mut pulse_query: Query<(Entity, &mut Transform, &mut GameTimer<HealPulse>), With<HealPulse>>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut timer) in &mut pulse_query {
+        timer.tick(time.delta());
+        transform.scale = Vec3::splat(0.5 + timer.fraction() * 0.5);
+
+        if timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
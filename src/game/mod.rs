@@ -1,18 +1,43 @@
+mod altitude;
+mod breath_ability;
+mod breath_charge;
 mod combat;
 mod constants;
+mod damage;
+mod death;
+mod difficulty;
+mod dragon_ability;
 mod enemy;
+mod entity_pool;
 mod fire_breath;
+mod fire_contagion;
+mod fire_wall;
+mod fireball;
 mod game_over;
 pub mod game_timer;
+mod heat;
+mod hit_feedback;
 mod hud;
 mod level;
+mod mage;
 mod player;
+mod player_hurt;
 mod plugin;
+mod population;
 mod power_up;
 mod resource_pool;
+mod scorch;
 mod score_system;
+mod shield;
+mod siege;
+mod spatial_hash;
+mod spatial_index;
+mod status_effect;
+mod steering;
+mod water;
 
 pub use constants::*;
+pub use difficulty::Difficulty;
 pub use enemy::Enemy;
 pub use level::{BorderTile, Tile};
 pub use player::Player;
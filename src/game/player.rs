@@ -3,7 +3,7 @@ use bevy_enhanced_input::prelude::Actions;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
-    animation::{AnimationIndices, AnimationTimer},
+    animation::SpriteAnimation,
     camera::{RenderLayer, YSorted},
     input::DefaultInputContext,
     physics::Speed,
@@ -11,8 +11,15 @@ use crate::{
 };
 
 use super::{
+    altitude::Altitude,
+    breath_ability::{BreathCooldowns, SelectedBreath},
+    breath_charge::BreathCharge,
+    damage::Invulnerability,
+    dragon_ability::AbilityCooldowns,
+    heat::Heat,
     resource_pool::{Fire, Health, ResourcePool},
     score_system::Score,
+    status_effect::StatusEffects,
     PLAYER_GROUP, POWERUP_GROUP, PROJECTILE_GROUP,
 };
 
@@ -27,28 +34,30 @@ impl Plugin for PlayerPlugin {
 #[derive(Component)]
 #[require(
     Actions::<DefaultInputContext>::default(),
-    AnimationIndices = Player::default_animation_indices(),
-    AnimationTimer::from_seconds(0.2),
     Collider::cuboid(15., 40.),
     CollisionGroups::new(PLAYER_GROUP, PROJECTILE_GROUP | POWERUP_GROUP),
     Speed(10.),
     ResourcePool::<Fire>::new(100),
     ResourcePool::<Health>::new(100),
+    Invulnerability::new(1.),
+    SelectedBreath,
+    BreathCooldowns,
+    BreathCharge,
+    AbilityCooldowns,
+    Altitude,
+    Heat,
+    StatusEffects,
     RenderLayers::layer(RenderLayer::Sky.into()),
     StateScoped::<AppState>(AppState::GameOver),
 )]
 pub struct Player;
 
-impl Player {
-    pub fn default_animation_indices() -> AnimationIndices {
-        AnimationIndices::new(0, 2)
-    }
-}
+/// Size of one frame of the dragon sprite sheet. The dragon is drawn facing along its width.
+pub const DRAGON_FRAME_SIZE: UVec2 = UVec2::new(191, 161);
 
 fn spawn_player(mut commands: Commands, asset_server: Res<AssetServer>) {
     let image = asset_server.load("textures/dragon.png");
-    let texture_atlas_layout =
-        TextureAtlasLayout::from_grid(UVec2::new(191, 161), 12, 1, None, None);
+    let texture_atlas_layout = TextureAtlasLayout::from_grid(DRAGON_FRAME_SIZE, 12, 1, None, None);
     let texture_atlas_layout_handle = asset_server.add(texture_atlas_layout);
 
     commands.spawn((
@@ -60,6 +69,7 @@ fn spawn_player(mut commands: Commands, asset_server: Res<AssetServer>) {
             texture_atlas: Some(texture_atlas_layout_handle.into()),
             ..Default::default()
         },
+        SpriteAnimation::new(asset_server.load("animations/dragon.anim.ron"), "fly"),
         Transform::from_translation(Vec2::ONE.extend(1.)),
         YSorted,
     ));
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::{PlaySoundEffectEvent, PlaybackSettings},
+    camera::CameraShakeEvent,
+    playing,
+};
+
+use super::{
+    damage::{DamageDealt, Invulnerability},
+    difficulty::Difficulty,
+    hit_feedback::Knockback,
+    Player,
+};
+
+const BLINK_INTERVAL_SECONDS: f32 = 0.08;
+const BLINK_ALPHA: f32 = 0.35;
+
+pub(super) struct PlayerHurtPlugin;
+
+impl Plugin for PlayerHurtPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HurtFeedback>();
+
+        app.add_systems(
+            FixedUpdate,
+            (
+                apply_difficulty,
+                react_to_player_damage,
+                blink_invulnerable_player,
+            )
+                .chain()
+                .run_if(playing()),
+        );
+    }
+}
+
+/// How hard getting hit knocks the player around, and how long they get to recover.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct HurtFeedback {
+    pub invulnerability_seconds: f32,
+    pub knockback_speed: f32,
+    /// Camera shake trauma added by each hit, from 0 to 1.
+    pub camera_shake: f32,
+}
+
+impl Default for HurtFeedback {
+    fn default() -> Self {
+        Self::for_difficulty(Difficulty::default())
+    }
+}
+
+impl HurtFeedback {
+    pub fn for_difficulty(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => Self {
+                invulnerability_seconds: 1.5,
+                knockback_speed: 220.,
+                camera_shake: 0.35,
+            },
+            Difficulty::Normal => Self {
+                invulnerability_seconds: 1.,
+                knockback_speed: 180.,
+                camera_shake: 0.45,
+            },
+            Difficulty::Hard => Self {
+                invulnerability_seconds: 0.5,
+                knockback_speed: 140.,
+                camera_shake: 0.55,
+            },
+        }
+    }
+}
+
+fn apply_difficulty(
+    difficulty: Res<Difficulty>,
+    mut hurt_feedback: ResMut<HurtFeedback>,
+    mut player_query: Query<(Ref<Player>, &mut Invulnerability)>,
+) {
+    if difficulty.is_changed() {
+        *hurt_feedback = HurtFeedback::for_difficulty(*difficulty);
+    }
+
+    for (player, mut invulnerability) in &mut player_query {
+        if difficulty.is_changed() || player.is_added() {
+            invulnerability.set_seconds(hurt_feedback.invulnerability_seconds);
+        }
+    }
+}
+
+fn react_to_player_damage(
+    mut commands: Commands,
+    mut damage_dealt_event_reader: EventReader<DamageDealt>,
+    mut play_sound_effect_event_writer: EventWriter<PlaySoundEffectEvent>,
+    mut camera_shake_event_writer: EventWriter<CameraShakeEvent>,
+    player: Single<(Entity, &Transform), With<Player>>,
+    hurt_feedback: Res<HurtFeedback>,
+) {
+    let (player_entity, player_transform) = player.into_inner();
+
+    for damage_dealt in damage_dealt_event_reader.read() {
+        if damage_dealt.target != player_entity {
+            continue;
+        }
+
+        let direction =
+            (player_transform.translation.xy() - damage_dealt.source_position).normalize_or_zero();
+
+        commands
+            .entity(player_entity)
+            .insert(Knockback::new(direction * hurt_feedback.knockback_speed));
+        camera_shake_event_writer.write(CameraShakeEvent::new(hurt_feedback.camera_shake));
+        // A slowed down, reversed breath makes for a pained grunt.
+        play_sound_effect_event_writer.write(PlaySoundEffectEvent::new(
+            "breathstart.ogg",
+            Some(PlaybackSettings {
+                playback_rate: 0.6,
+                reverse: true,
+                volume: 0.6,
+                ..default()
+            }),
+            None,
+        ));
+    }
+}
+
+/// Blinks the player while they can't be hurt.
+fn blink_invulnerable_player(player: Single<(&Invulnerability, &mut Sprite), With<Player>>) {
+    let (invulnerability, mut sprite) = player.into_inner();
+
+    let alpha = if invulnerability.is_active()
+        && ((invulnerability.elapsed_secs() / BLINK_INTERVAL_SECONDS) as u32).is_multiple_of(2)
+    {
+        BLINK_ALPHA
+    } else {
+        1.
+    };
+
+    if sprite.color.alpha() != alpha {
+        sprite.color.set_alpha(alpha);
+    }
+}
@@ -1,9 +1,16 @@
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
 use super::{
-    combat::CombatPlugin, enemy::EnemyPlugin, fire_breath::FireBreathPlugin,
-    game_over::GameOverPlugin, hud::HudPlugin, level::LevelPlugin, player::PlayerPlugin,
-    power_up::PowerUpSystemPlugin, score_system::ScoreSystemPlugin,
+    altitude::AltitudePlugin, breath_ability::BreathAbilityPlugin,
+    breath_charge::BreathChargePlugin, combat::CombatPlugin, damage::DamagePlugin,
+    death::DeathPlugin, difficulty::DifficultyPlugin, dragon_ability::DragonAbilityPlugin,
+    enemy::EnemyPlugin, fire_breath::FireBreathPlugin, fire_contagion::FireContagionPlugin,
+    fire_wall::FireWallPlugin, fireball::FireballPlugin, game_over::GameOverPlugin,
+    heat::HeatPlugin, hit_feedback::HitFeedbackPlugin, hud::HudPlugin, level::LevelPlugin,
+    mage::MagePlugin, player::PlayerPlugin, player_hurt::PlayerHurtPlugin,
+    population::PopulationPlugin, power_up::PowerUpSystemPlugin, scorch::ScorchPlugin,
+    score_system::ScoreSystemPlugin, shield::ShieldPlugin, siege::SiegePlugin,
+    spatial_index::SpatialIndexPlugin, status_effect::StatusEffectPlugin, water::WaterPlugin,
 };
 
 pub struct GamePlugin;
@@ -11,14 +18,35 @@ pub struct GamePlugin;
 impl PluginGroup for GamePlugin {
     fn build(self) -> bevy::app::PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
+            .add(AltitudePlugin)
+            .add(BreathAbilityPlugin)
+            .add(BreathChargePlugin)
             .add(CombatPlugin)
+            .add(DamagePlugin)
+            .add(DeathPlugin)
+            .add(DifficultyPlugin)
+            .add(DragonAbilityPlugin)
             .add(EnemyPlugin)
             .add(FireBreathPlugin)
+            .add(FireContagionPlugin)
+            .add(FireWallPlugin)
+            .add(FireballPlugin)
             .add(GameOverPlugin)
+            .add(HeatPlugin)
+            .add(HitFeedbackPlugin)
             .add(HudPlugin)
             .add(LevelPlugin)
+            .add(MagePlugin)
             .add(PlayerPlugin)
+            .add(PlayerHurtPlugin)
+            .add(PopulationPlugin)
             .add(PowerUpSystemPlugin)
+            .add(ScorchPlugin)
             .add(ScoreSystemPlugin)
+            .add(ShieldPlugin)
+            .add(SiegePlugin)
+            .add(SpatialIndexPlugin)
+            .add(StatusEffectPlugin)
+            .add(WaterPlugin)
     }
 }
@@ -0,0 +1,173 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+use rand::Rng;
+
+use crate::{camera::MainCamera, playing, AppState};
+
+use super::{
+    combat::{OnFire, Projectile},
+    death::Despawn,
+    enemy::Behavior,
+    entity_pool::EntityPool,
+    game_timer::GameTimer,
+    Enemy, Player, GRID_SIZE, HALF_TILE_SIZE, TILE_SIZE,
+};
+
+pub const LIVE_ENEMIES_DIAGNOSTIC: DiagnosticPath = DiagnosticPath::const_new("enemies/live");
+pub const ENEMY_POOL_DIAGNOSTIC: DiagnosticPath = DiagnosticPath::const_new("pool/enemies");
+pub const PROJECTILE_POOL_DIAGNOSTIC: DiagnosticPath =
+    DiagnosticPath::const_new("pool/projectiles");
+
+pub(super) struct PopulationPlugin;
+
+impl Plugin for PopulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyPopulation>();
+        app.init_resource::<EntityPool<Enemy>>();
+        app.init_resource::<EntityPool<Projectile>>();
+
+        app.register_diagnostic(Diagnostic::new(LIVE_ENEMIES_DIAGNOSTIC));
+        app.register_diagnostic(Diagnostic::new(ENEMY_POOL_DIAGNOSTIC));
+        app.register_diagnostic(Diagnostic::new(PROJECTILE_POOL_DIAGNOSTIC));
+
+        app.add_systems(OnEnter(AppState::InGame), clear_entity_pools);
+
+        app.add_systems(
+            FixedUpdate,
+            (recycle_distant_enemies, recycle_distant_projectiles).run_if(playing()),
+        );
+
+        app.add_systems(Update, update_population_diagnostics.run_if(playing()));
+    }
+}
+
+/// Limits on how many enemies are alive at once and how far they can stray from the player.
+#[derive(Resource)]
+pub struct EnemyPopulation {
+    pub max_live_enemies: usize,
+    /// Distance from the player beyond which enemies and projectiles are considered far off-screen.
+    pub recycle_distance: f32,
+    /// How long an enemy may stay far off-screen before it is brought back near the player.
+    pub recycle_seconds: f32,
+}
+
+impl Default for EnemyPopulation {
+    fn default() -> Self {
+        Self {
+            max_live_enemies: 150,
+            recycle_distance: TILE_SIZE.x * 80.,
+            recycle_seconds: 8.,
+        }
+    }
+}
+
+fn clear_entity_pools(
+    mut enemy_pool: ResMut<EntityPool<Enemy>>,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
+) {
+    enemy_pool.clear();
+    projectile_pool.clear();
+}
+
+/// Burning enemies far away are returned to the pool, everyone else is moved back to the edge
+/// of the screen so crowds don't pile up where the player can't see them.
+fn recycle_distant_enemies(
+    mut commands: Commands,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            Has<OnFire>,
+            Option<&mut GameTimer<EnemyPopulation>>,
+        ),
+        (With<Enemy>, With<Behavior>),
+    >,
+    player_transform: Single<&Transform, (With<Player>, Without<Enemy>)>,
+    camera: Single<&Camera, With<MainCamera>>,
+    enemy_population: Res<EnemyPopulation>,
+    time: Res<Time>,
+) {
+    let player_position = player_transform.translation.xy();
+    let screen_radius = camera
+        .logical_viewport_size()
+        .map_or(TILE_SIZE.x * 40., |viewport_size| {
+            viewport_size.length() / 2.
+        });
+    let level_half_size = GRID_SIZE * TILE_SIZE / 2. - TILE_SIZE;
+    let mut rng = rand::rng();
+
+    for (entity, mut transform, is_on_fire, recycle_timer) in &mut enemy_query {
+        let distance = transform.translation.xy().distance(player_position);
+
+        if distance <= enemy_population.recycle_distance {
+            if recycle_timer.is_some() {
+                commands
+                    .entity(entity)
+                    .remove::<GameTimer<EnemyPopulation>>();
+            }
+            continue;
+        }
+
+        if is_on_fire {
+            commands.entity(entity).insert(Despawn);
+            continue;
+        }
+
+        let Some(mut recycle_timer) = recycle_timer else {
+            commands
+                .entity(entity)
+                .insert(GameTimer::<EnemyPopulation>::from_seconds_once(
+                    enemy_population.recycle_seconds,
+                ));
+            continue;
+        };
+
+        if recycle_timer.tick(time.delta()).just_finished() {
+            let direction = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU));
+            let position = (player_position + direction * (screen_radius + TILE_SIZE.x * 2.))
+                .clamp(
+                    -level_half_size - HALF_TILE_SIZE,
+                    level_half_size - HALF_TILE_SIZE,
+                );
+
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            commands
+                .entity(entity)
+                .remove::<GameTimer<EnemyPopulation>>();
+        }
+    }
+}
+
+fn recycle_distant_projectiles(
+    mut commands: Commands,
+    projectile_query: Query<(Entity, &Transform), With<Projectile>>,
+    player_transform: Single<&Transform, (With<Player>, Without<Projectile>)>,
+    enemy_population: Res<EnemyPopulation>,
+) {
+    let player_position = player_transform.translation.xy();
+
+    for (entity, transform) in &projectile_query {
+        if transform.translation.xy().distance(player_position) > enemy_population.recycle_distance
+        {
+            commands.entity(entity).insert(Despawn);
+        }
+    }
+}
+
+fn update_population_diagnostics(
+    mut diagnostics: Diagnostics,
+    enemy_query: Query<(), (With<Enemy>, With<Behavior>)>,
+    enemy_pool: Res<EntityPool<Enemy>>,
+    projectile_pool: Res<EntityPool<Projectile>>,
+) {
+    diagnostics.add_measurement(&LIVE_ENEMIES_DIAGNOSTIC, || {
+        enemy_query.iter().count() as f64
+    });
+    diagnostics.add_measurement(&ENEMY_POOL_DIAGNOSTIC, || enemy_pool.available() as f64);
+    diagnostics.add_measurement(&PROJECTILE_POOL_DIAGNOSTIC, || {
+        projectile_pool.available() as f64
+    });
+}
@@ -7,13 +7,15 @@ use bevy_rapier2d::{
 use rand::Rng;
 
 use crate::{
-    animation::{AnimationIndices, AnimationTimer},
+    animation::SpriteAnimation,
     camera::{RenderLayer, YSorted},
     playing, AppState,
 };
 
 use super::{
+    death::{DeathCause, DeathEvent, Despawn, Rescued},
     resource_pool::{Health, ResourcePool},
+    status_effect::{ApplyStatusEffect, COOLED, SHIELDED},
     Player, HALF_TILE_SIZE, PLAYER_GROUP, POWERUP_GROUP,
 };
 
@@ -25,7 +27,9 @@ impl Plugin for PowerUpSystemPlugin {
         app.add_systems(OnEnter(AppState::InGame), load_scale_atlas);
         app.add_systems(
             FixedUpdate,
-            (spawn_powerups, consume_powerups).run_if(playing()),
+            (drop_powerups, spawn_powerups, consume_powerups)
+                .chain()
+                .run_if(playing()),
         );
     }
 }
@@ -52,8 +56,6 @@ impl PowerUpEvent {
 
 #[derive(Component)]
 #[require(
-    AnimationIndices::new(0, 1),
-    AnimationTimer::from_seconds(0.2),
     RenderLayers::layer(RenderLayer::Sky.into()),
     Collider::cuboid(HALF_TILE_SIZE.x, HALF_TILE_SIZE.y),
     Sensor,
@@ -78,6 +80,22 @@ fn load_scale_atlas(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 }
 
+/// Anything slain may leave a healing scale behind, once.
+fn drop_powerups(
+    mut death_event_reader: EventReader<DeathEvent>,
+    mut powerup_event_writer: EventWriter<PowerUpEvent>,
+    rescued_query: Query<(), With<Rescued>>,
+) {
+    for death_event in death_event_reader.read() {
+        if death_event.cause == DeathCause::Slain && !rescued_query.contains(death_event.entity) {
+            powerup_event_writer.write(PowerUpEvent::new(
+                Transform::from_translation(death_event.position.extend(1.)),
+                PowerUpEventType::HealingScale,
+            ));
+        }
+    }
+}
+
 fn spawn_powerups(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -85,6 +103,7 @@ fn spawn_powerups(
     mut powerup_event_reader: EventReader<PowerUpEvent>,
 ) {
     let texture_healing_scale = asset_server.load("textures/scale_anim.png");
+    let animation_healing_scale = asset_server.load("animations/scale.anim.ron");
 
     for PowerUpEvent {
         transform,
@@ -103,6 +122,7 @@ fn spawn_powerups(
                             texture_atlas: Some(scale_texture_atlas_handler.0.clone().into()),
                             ..Default::default()
                         },
+                        SpriteAnimation::new(animation_healing_scale.clone(), "spin"),
                         *transform,
                         YSorted,
                     ));
@@ -112,21 +132,35 @@ fn spawn_powerups(
     }
 }
 
+/// Healing scales also harden the dragon's hide and cool its breath for a moment.
 fn consume_powerups(
     mut commands: Commands,
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffect>,
     powerup_query: Query<Entity, With<PowerUp>>,
-    mut player_hp: Single<&mut ResourcePool<Health>, With<Player>>,
+    player: Single<(Entity, &mut ResourcePool<Health>), With<Player>>,
     rapier_context: ReadRapierContext,
 ) {
     let Ok(rapier_context) = rapier_context.single() else {
         return;
     };
 
+    let (player_entity, mut player_hp) = player.into_inner();
+
     for entity in &powerup_query {
         for (_, _, intersecting) in rapier_context.intersection_pairs_with(entity) {
             if intersecting {
                 player_hp.add(50);
-                commands.entity(entity).despawn();
+                apply_status_effect_event_writer.write(ApplyStatusEffect::new(
+                    player_entity,
+                    SHIELDED,
+                    None,
+                ));
+                apply_status_effect_event_writer.write(ApplyStatusEffect::new(
+                    player_entity,
+                    COOLED,
+                    None,
+                ));
+                commands.entity(entity).insert(Despawn);
             }
         }
     }
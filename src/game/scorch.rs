@@ -0,0 +1,348 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        view::RenderLayers,
+    },
+};
+use rand::Rng;
+
+use crate::{camera::RenderLayer, playing, AppState};
+
+use super::{
+    breath_ability::{CONE_HALF_ANGLE, CONE_MOUTH_HALF_WIDTH, CONE_RANGE},
+    combat::OnFire,
+    death::{DeathCause, DeathEvent, Despawn},
+    fire_breath::BreathCone,
+    fire_wall::FireWallSegment,
+    fireball::Fireball,
+    game_timer::GameTimer,
+    level::{Building, LevelMatrix, Tile},
+    HALF_TILE_SIZE, TILE_SIZE,
+};
+
+/// Fading decals kept at once. Past this the oldest one is moved to where the new one goes.
+const MAX_FADING_DECALS: usize = 400;
+const FADE_SECONDS: f32 = 45.;
+/// Seconds between two marks left under the breath and under everything burning.
+const TRAIL_INTERVAL: f32 = 0.15;
+/// Marks left inside the cone each time the trail interval runs out.
+const CONE_MARKS: usize = 2;
+/// Above the ground tiles, below the waves.
+const DECAL_Z: f32 = 0.5;
+const DECAL_TEXTURE_SIZE: u32 = 32;
+
+pub(super) struct ScorchPlugin;
+
+impl Plugin for ScorchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScorchAssets>();
+        app.init_resource::<ScorchDecals>();
+        app.insert_resource(GameTimer::<ScorchTrail>::from_seconds(TRAIL_INTERVAL));
+
+        app.add_event::<ScorchEvent>();
+
+        app.add_systems(OnEnter(AppState::InGame), clear_scorch_decals);
+        app.add_systems(
+            FixedUpdate,
+            (
+                (
+                    (scorch_under_breath, scorch_under_burning_entities).chain(),
+                    scorch_fire_wall_segments,
+                    scorch_burned_out_units,
+                ),
+                spawn_scorch_decals,
+                fade_scorch_decals,
+            )
+                .chain()
+                .run_if(playing()),
+        );
+
+        app.add_observer(scorch_fireball_explosions);
+        app.add_observer(scorch_ruins);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScorchKind {
+    /// Blackened ground the breath passed over.
+    Scorch,
+    /// What is left under burning units and where they burned to death.
+    Ash,
+    /// Where a building burned down. Never fades, so a burned village stays burned.
+    Ruin,
+}
+
+impl ScorchKind {
+    fn color(&self) -> Color {
+        match self {
+            Self::Scorch => Color::srgba(0.08, 0.05, 0.03, 0.7),
+            Self::Ash => Color::srgba(0.35, 0.33, 0.3, 0.6),
+            Self::Ruin => Color::srgba(0.1, 0.08, 0.06, 0.85),
+        }
+    }
+}
+
+/// Asks for a mark of `kind` to be left on the ground.
+#[derive(Event)]
+pub struct ScorchEvent {
+    pub position: Vec2,
+    pub radius: f32,
+    pub kind: ScorchKind,
+}
+
+impl ScorchEvent {
+    pub fn new(position: Vec2, radius: f32, kind: ScorchKind) -> Self {
+        Self {
+            position,
+            radius,
+            kind,
+        }
+    }
+}
+
+/// Every decal shares the same image and only differs in color, size and rotation, so they
+/// are all drawn in a single batch.
+#[derive(Resource)]
+struct ScorchAssets {
+    image: Handle<Image>,
+}
+
+impl FromWorld for ScorchAssets {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            image: world.resource_mut::<Assets<Image>>().add(soft_disc_image()),
+        }
+    }
+}
+
+/// White disc that is solid in the middle and fades out towards the edge, to be tinted.
+pub fn soft_disc_image() -> Image {
+    let center = (DECAL_TEXTURE_SIZE as f32 - 1.) * 0.5;
+    let data = (0..DECAL_TEXTURE_SIZE * DECAL_TEXTURE_SIZE)
+        .flat_map(|index| {
+            let offset = Vec2::new(
+                (index % DECAL_TEXTURE_SIZE) as f32 - center,
+                (index / DECAL_TEXTURE_SIZE) as f32 - center,
+            );
+            let falloff = 1. - (offset.length() / center).min(1.);
+            let alpha = (falloff * 2.).min(1.).powi(2);
+
+            [255, 255, 255, (alpha * 255.) as u8]
+        })
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: DECAL_TEXTURE_SIZE,
+            height: DECAL_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Fading decals, oldest first.
+#[derive(Resource, Default)]
+struct ScorchDecals(VecDeque<Entity>);
+
+/// Fades the decal out, after which it stays hidden until it is reused.
+#[derive(Component)]
+struct ScorchFade {
+    timer: Timer,
+    alpha: f32,
+}
+
+struct ScorchTrail;
+
+fn clear_scorch_decals(mut decals: ResMut<ScorchDecals>) {
+    decals.0.clear();
+}
+
+fn scorch_under_breath(
+    mut scorch_event_writer: EventWriter<ScorchEvent>,
+    mut trail_timer: ResMut<GameTimer<ScorchTrail>>,
+    cone_query: Query<&GlobalTransform, With<BreathCone>>,
+    time: Res<Time>,
+) {
+    if !trail_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let mut rng = rand::rng();
+
+    for cone_transform in &cone_query {
+        for _ in 0..CONE_MARKS {
+            let distance = rng.random_range(0. ..=CONE_RANGE);
+            let half_width = CONE_MOUTH_HALF_WIDTH + distance * CONE_HALF_ANGLE.tan();
+            let local_position =
+                Vec3::new(rng.random_range(-half_width..=half_width), distance, 0.);
+
+            scorch_event_writer.write(ScorchEvent::new(
+                cone_transform.transform_point(local_position).xy(),
+                rng.random_range(HALF_TILE_SIZE.x..=TILE_SIZE.x),
+                ScorchKind::Scorch,
+            ));
+        }
+    }
+}
+
+/// Shares the trail timer with the breath, which already ticked it this tick.
+fn scorch_under_burning_entities(
+    mut scorch_event_writer: EventWriter<ScorchEvent>,
+    trail_timer: Res<GameTimer<ScorchTrail>>,
+    query: Query<&Transform, (With<OnFire>, Without<Building>)>,
+) {
+    if !trail_timer.just_finished() {
+        return;
+    }
+
+    for transform in &query {
+        scorch_event_writer.write(ScorchEvent::new(
+            transform.translation.xy(),
+            HALF_TILE_SIZE.x,
+            ScorchKind::Ash,
+        ));
+    }
+}
+
+fn scorch_fire_wall_segments(
+    mut scorch_event_writer: EventWriter<ScorchEvent>,
+    query: Query<&Transform, Added<FireWallSegment>>,
+) {
+    for transform in &query {
+        scorch_event_writer.write(ScorchEvent::new(
+            transform.translation.xy(),
+            TILE_SIZE.x * 0.75,
+            ScorchKind::Scorch,
+        ));
+    }
+}
+
+fn scorch_burned_out_units(
+    mut death_event_reader: EventReader<DeathEvent>,
+    mut scorch_event_writer: EventWriter<ScorchEvent>,
+) {
+    for death_event in death_event_reader.read() {
+        if death_event.cause != DeathCause::Slain {
+            scorch_event_writer.write(ScorchEvent::new(
+                death_event.position,
+                TILE_SIZE.x * 0.75,
+                ScorchKind::Ash,
+            ));
+        }
+    }
+}
+
+/// Fireballs blow up whenever they are removed, whether they hit something or ran out of time.
+fn scorch_fireball_explosions(
+    trigger: Trigger<OnRemove, Fireball>,
+    mut scorch_event_writer: EventWriter<ScorchEvent>,
+    query: Query<(&Transform, &Fireball)>,
+) {
+    if let Ok((transform, fireball)) = query.get(trigger.target()) {
+        scorch_event_writer.write(ScorchEvent::new(
+            transform.translation.xy(),
+            fireball.explosion_radius(),
+            ScorchKind::Scorch,
+        ));
+    }
+}
+
+/// Buildings are despawned as soon as they come down, while they can still be told apart.
+fn scorch_ruins(
+    trigger: Trigger<OnAdd, Despawn>,
+    mut scorch_event_writer: EventWriter<ScorchEvent>,
+    query: Query<&Transform, With<Building>>,
+) {
+    if let Ok(transform) = query.get(trigger.target()) {
+        scorch_event_writer.write(ScorchEvent::new(
+            transform.translation.xy(),
+            TILE_SIZE.x * 1.5,
+            ScorchKind::Ruin,
+        ));
+    }
+}
+
+fn spawn_scorch_decals(
+    mut commands: Commands,
+    mut scorch_event_reader: EventReader<ScorchEvent>,
+    mut decals: ResMut<ScorchDecals>,
+    scorch_assets: Res<ScorchAssets>,
+    level_matrix: Res<LevelMatrix>,
+) {
+    let mut rng = rand::rng();
+
+    for &ScorchEvent {
+        position,
+        radius,
+        kind,
+    } in scorch_event_reader.read()
+    {
+        if level_matrix.tile_at(position) == Some(Tile::Water) {
+            continue;
+        }
+
+        let color = kind.color();
+        let decal = (
+            Sprite {
+                image: scorch_assets.image.clone(),
+                color,
+                custom_size: Some(Vec2::splat(radius * 2.)),
+                ..default()
+            },
+            Transform::from_translation(position.extend(DECAL_Z)).with_rotation(
+                Quat::from_rotation_z(rng.random_range(0. ..std::f32::consts::TAU)),
+            ),
+            Visibility::Inherited,
+            RenderLayers::layer(RenderLayer::Background.into()),
+        );
+
+        if kind == ScorchKind::Ruin {
+            commands.spawn((decal, StateScoped(AppState::GameOver)));
+            continue;
+        }
+
+        let fade = ScorchFade {
+            timer: Timer::from_seconds(FADE_SECONDS, TimerMode::Once),
+            alpha: color.alpha(),
+        };
+
+        let entity = if decals.0.len() >= MAX_FADING_DECALS {
+            let oldest = decals.0.pop_front().expect("the decal cap is above zero");
+            commands.entity(oldest).insert((decal, fade));
+            oldest
+        } else {
+            commands
+                .spawn((decal, fade, StateScoped(AppState::GameOver)))
+                .id()
+        };
+        decals.0.push_back(entity);
+    }
+}
+
+fn fade_scorch_decals(
+    mut query: Query<(&mut Sprite, &mut Visibility, &mut ScorchFade)>,
+    time: Res<Time>,
+) {
+    for (mut sprite, mut visibility, mut fade) in &mut query {
+        if fade.timer.finished() {
+            continue;
+        }
+
+        fade.timer.tick(time.delta());
+        sprite
+            .color
+            .set_alpha(fade.alpha * fade.timer.fraction_remaining());
+
+        if fade.timer.finished() {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
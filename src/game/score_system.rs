@@ -6,14 +6,20 @@ use bevy::{
 
 use crate::playing;
 
-use super::Player;
+use super::{
+    death::{DeathCause, DeathEvent, Rescued},
+    Player,
+};
 
 pub(super) struct ScoreSystemPlugin;
 
 impl Plugin for ScoreSystemPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ScoreEvent>();
-        app.add_systems(Update, update_player_score.run_if(playing()));
+        app.add_systems(
+            Update,
+            (score_kills, update_player_score).chain().run_if(playing()),
+        );
     }
 }
 
@@ -81,6 +87,28 @@ impl ScoreEvent {
     }
 }
 
+fn score_kills(
+    mut death_event_reader: EventReader<DeathEvent>,
+    mut score_event_writer: EventWriter<ScoreEvent>,
+    player_entity: Single<Entity, With<Player>>,
+    rescued_query: Query<(), With<Rescued>>,
+) {
+    for death_event in death_event_reader.read() {
+        if death_event.killer != Some(*player_entity) || rescued_query.contains(death_event.entity)
+        {
+            continue;
+        }
+
+        let points = match death_event.cause {
+            DeathCause::Slain => 10,
+            DeathCause::Finished => 5,
+            DeathCause::BurnedOut => continue,
+        };
+
+        score_event_writer.write(ScoreEvent::new(points, ScoreEventType::AddPoints));
+    }
+}
+
 fn update_player_score(
     mut score_event_reader: EventReader<ScoreEvent>,
     mut player_score: Single<&mut Score, With<Player>>,
@@ -0,0 +1,154 @@
+use bevy::{prelude::*, render::view::RenderLayers};
+
+use crate::{camera::RenderLayer, playing};
+
+use super::{enemy::FacingDirection, game_timer::GameTimer};
+
+const SHIELD_VISUAL_SIZE: Vec2 = Vec2::new(4., 12.);
+const SHIELD_VISUAL_OFFSET: f32 = 10.;
+/// Radians per second the shield turns to follow its carrier, so it can be flanked.
+const SHIELD_TURN_RATE: f32 = 2.;
+
+pub(super) struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                repair_broken_shields,
+                (turn_shields, update_shield_visuals).chain(),
+            )
+                .run_if(playing()),
+        );
+
+        app.add_observer(on_add_shield);
+    }
+}
+
+/// Blocks most damage coming from the direction the shield is held towards, which lags
+/// behind where its carrier faces. Blocked damage wears the shield down until it breaks.
+#[derive(Component)]
+pub struct Shield {
+    /// Direction the shield is held towards.
+    guard: Dir2,
+    /// Fraction of frontal damage absorbed by the shield.
+    block_ratio: f32,
+    /// Cosine of the half-angle of the arc the shield covers in front of its carrier.
+    coverage: f32,
+    durability: f32,
+    max_durability: f32,
+    /// Unblocked damage smaller than a whole hitpoint, carried over to the next hit.
+    damage_remainder: f32,
+}
+
+impl Default for Shield {
+    fn default() -> Self {
+        Self {
+            guard: Dir2::WEST,
+            block_ratio: 0.85,
+            coverage: 60_f32.to_radians().cos(),
+            durability: 60.,
+            max_durability: 60.,
+            damage_remainder: 0.,
+        }
+    }
+}
+
+impl Shield {
+    /// Returns the damage that goes through the shield for a hit arriving along `incoming`.
+    pub fn absorb(&mut self, damage: i16, incoming: Vec2) -> i16 {
+        let is_frontal = self.guard.dot(-incoming.normalize_or_zero()) >= self.coverage;
+
+        if !is_frontal || self.is_broken() {
+            return damage;
+        }
+
+        let blocked = damage as f32 * self.block_ratio;
+        let unblocked = damage as f32 - blocked + self.damage_remainder;
+
+        self.durability = (self.durability - blocked).max(0.);
+        self.damage_remainder = unblocked.fract();
+
+        unblocked.trunc() as i16
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.durability == 0.
+    }
+}
+
+#[derive(Component)]
+pub struct ShieldVisual;
+
+fn on_add_shield(trigger: Trigger<OnAdd, Shield>, mut commands: Commands) {
+    commands.entity(trigger.target()).with_child((
+        ShieldVisual,
+        Sprite::from_color(Color::srgb(0.6, 0.62, 0.68), SHIELD_VISUAL_SIZE),
+        Transform::from_xyz(-SHIELD_VISUAL_OFFSET, 0., 0.1),
+        RenderLayers::layer(RenderLayer::Ground.into()),
+    ));
+}
+
+/// Broken shields stay down for a while before their carrier raises them again.
+fn repair_broken_shields(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Shield, Option<&mut GameTimer<Shield>>)>,
+    time: Res<Time>,
+) {
+    const SHIELD_REPAIR_SECONDS: f32 = 5.;
+
+    for (entity, mut shield, timer) in &mut query {
+        if !shield.is_broken() {
+            continue;
+        }
+
+        match timer {
+            Some(mut timer) => {
+                if timer.tick(time.delta()).just_finished() {
+                    shield.durability = shield.max_durability;
+                    commands.entity(entity).remove::<GameTimer<Shield>>();
+                }
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(GameTimer::<Shield>::from_seconds_once(
+                        SHIELD_REPAIR_SECONDS,
+                    ));
+            }
+        }
+    }
+}
+
+fn turn_shields(mut query: Query<(&FacingDirection, &mut Shield)>, time: Res<Time>) {
+    let max_angle = SHIELD_TURN_RATE * time.delta_secs();
+
+    for (facing_direction, mut shield) in &mut query {
+        let angle = shield
+            .guard
+            .rotation_to(**facing_direction)
+            .as_radians()
+            .clamp(-max_angle, max_angle);
+        shield.guard = Rot2::radians(angle) * shield.guard;
+    }
+}
+
+fn update_shield_visuals(
+    shield_query: Query<(&Shield, &Children)>,
+    mut visual_query: Query<(&mut Transform, &mut Visibility), With<ShieldVisual>>,
+) {
+    for (shield, children) in &shield_query {
+        let mut visuals = visual_query.iter_many_mut(children);
+
+        while let Some((mut transform, mut visibility)) = visuals.fetch_next() {
+            transform.translation = (shield.guard * SHIELD_VISUAL_OFFSET).extend(0.1);
+            transform.rotation = Quat::from_rotation_z(shield.guard.to_angle());
+            visibility.set_if_neq(if shield.is_broken() {
+                Visibility::Hidden
+            } else {
+                Visibility::Inherited
+            });
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use bevy::{color::palettes::css::RED, prelude::*, render::view::RenderLayers};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    camera::{RenderLayer, YSorted},
+    physics::Speed,
+    playing, AppState,
+};
+
+use super::{
+    combat::{
+        AttackDamage, AttackProjectile, AttackTimer, ProjectileKind, Range, SpawnProjectileEvent,
+    },
+    damage::{Damage, DamageType, Resistances},
+    enemy::{AttackPhase, Behavior, FacingDirection},
+    fire_contagion::Flammable,
+    resource_pool::{Health, ResourcePool},
+    Enemy, Player, BUILDING_GROUP, ENEMY_GROUP, FIRE_BREATH_GROUP, TILE_SIZE,
+};
+
+pub(super) struct SiegePlugin;
+
+impl Plugin for SiegePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (aim_siege_engines, remove_aim_lines.after(aim_siege_engines)).run_if(playing()),
+        );
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SiegeEngine {
+    /// Fires a piercing bolt along a line shown to the player while it aims.
+    Ballista,
+    /// Lobs a boulder at the spot the player was standing, marked on the ground until it lands.
+    Catapult,
+}
+
+impl SiegeEngine {
+    const SIZE: Vec2 = Vec2::new(TILE_SIZE.x * 2., TILE_SIZE.y * 1.5);
+
+    fn aim_seconds(&self) -> f32 {
+        match self {
+            Self::Ballista => 1.2,
+            Self::Catapult => 0.6,
+        }
+    }
+
+    fn projectile(&self) -> ProjectileKind {
+        match self {
+            Self::Ballista => ProjectileKind::Bolt,
+            Self::Catapult => ProjectileKind::Boulder,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Self::Ballista => Color::srgb(0.45, 0.3, 0.15),
+            Self::Catapult => Color::srgb(0.35, 0.22, 0.1),
+        }
+    }
+}
+
+/// Point a siege engine locked onto when it started aiming.
+#[derive(Component)]
+pub struct SiegeAim {
+    target: Vec2,
+    timer: Timer,
+}
+
+#[derive(Component)]
+pub struct AimLine;
+
+pub fn siege_engine_bundle(siege_engine: SiegeEngine, translation: Vec3) -> impl Bundle {
+    (
+        (
+            siege_engine,
+            Sprite::from_color(siege_engine.color(), SiegeEngine::SIZE),
+            Transform::from_translation(translation),
+            AttackDamage(match siege_engine {
+                SiegeEngine::Ballista => Damage::new(15, DamageType::Piercing),
+                SiegeEngine::Catapult => Damage::new(20, DamageType::Blunt),
+            }),
+            AttackProjectile(siege_engine.projectile()),
+            AttackTimer::new(5.),
+            AttackPhase::default(),
+            Flammable::new(0.5),
+            // Timber frames barely notice arrows but go up in flames
+            Resistances::default()
+                .with(DamageType::Fire, 1.5)
+                .with(DamageType::Piercing, 0.25),
+            Behavior::FollowPlayer {
+                distance: TILE_SIZE.x * 14.,
+            },
+            ResourcePool::<Health>::new(200),
+            Enemy,
+            Range(TILE_SIZE.x * 25.),
+            Speed(30.),
+        ),
+        (
+            RenderLayers::layer(RenderLayer::Ground.into()),
+            FacingDirection::default(),
+            StateScoped(AppState::GameOver),
+            YSorted,
+            Collider::cuboid(SiegeEngine::SIZE.x / 2., SiegeEngine::SIZE.y / 2.),
+            RigidBody::Dynamic,
+            CollisionGroups::new(
+                ENEMY_GROUP,
+                ENEMY_GROUP | BUILDING_GROUP | FIRE_BREATH_GROUP,
+            ),
+            LockedAxes::ROTATION_LOCKED,
+        ),
+    )
+}
+
+fn aim_siege_engines(
+    mut commands: Commands,
+    mut spawn_projectile_event_writer: EventWriter<SpawnProjectileEvent>,
+    mut siege_query: Query<(
+        Entity,
+        &Transform,
+        &SiegeEngine,
+        &AttackDamage,
+        &AttackProjectile,
+        &mut AttackPhase,
+        Option<&mut SiegeAim>,
+    )>,
+    player_transform: Single<&Transform, (With<Player>, Without<SiegeEngine>)>,
+    time: Res<Time>,
+) {
+    let player_position = player_transform.translation.xy();
+
+    for (
+        entity,
+        transform,
+        siege_engine,
+        attack_damage,
+        attack_projectile,
+        mut attack_phase,
+        siege_aim,
+    ) in &mut siege_query
+    {
+        if *attack_phase != AttackPhase::WindUp {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+
+        let Some(mut siege_aim) = siege_aim else {
+            let aim = player_position - position;
+
+            commands.entity(entity).insert(SiegeAim {
+                target: player_position,
+                timer: Timer::from_seconds(siege_engine.aim_seconds(), TimerMode::Once),
+            });
+
+            if *siege_engine == SiegeEngine::Ballista {
+                commands.entity(entity).with_child((
+                    AimLine,
+                    Sprite::from_color(RED.with_alpha(0.5), Vec2::new(aim.length(), 2.)),
+                    Transform::from_translation((aim / 2.).extend(0.1))
+                        .with_rotation(Quat::from_rotation_z(aim.to_angle())),
+                    RenderLayers::layer(RenderLayer::Ground.into()),
+                ));
+            }
+            continue;
+        };
+
+        if !siege_aim.timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        spawn_projectile_event_writer.write(
+            SpawnProjectileEvent::new(attack_damage.0, entity, position, siege_aim.target)
+                .with_kind(attack_projectile.0),
+        );
+
+        *attack_phase = AttackPhase::Ready;
+        commands.entity(entity).remove::<SiegeAim>();
+    }
+}
+
+fn remove_aim_lines(
+    mut commands: Commands,
+    aim_line_query: Query<(Entity, &ChildOf), With<AimLine>>,
+    siege_query: Query<&AttackPhase, With<SiegeEngine>>,
+) {
+    for (entity, child_of) in &aim_line_query {
+        if siege_query
+            .get(child_of.parent())
+            .is_ok_and(|attack_phase| *attack_phase != AttackPhase::WindUp)
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
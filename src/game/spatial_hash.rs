@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+/// Uniform grid bucketing entities by position so proximity queries only visit nearby cells.
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        if cell_size <= 0. {
+            panic!("SpatialHash cell size cannot be zero or less.");
+        }
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Empties every cell while keeping their allocations for the next rebuild.
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2) {
+        self.cells
+            .entry(self.cell_coordinates(position))
+            .or_default()
+            .push((entity, position));
+    }
+
+    /// Iterates over every entry within `radius` of `position`.
+    pub fn query_radius(
+        &self,
+        position: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = self.cell_coordinates(position - radius);
+        let max = self.cell_coordinates(position + radius);
+        let radius_squared = radius * radius;
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(_, other_position)| {
+                position.distance_squared(*other_position) <= radius_squared
+            })
+    }
+
+    /// Returns up to `k` entries within `max_radius` of `position`, closest first.
+    ///
+    /// Cells are visited in rings around `position` and the search stops as soon as no
+    /// unvisited ring can hold anything closer than the entries already found.
+    pub fn nearest_k(&self, position: Vec2, k: usize, max_radius: f32) -> Vec<(Entity, Vec2)> {
+        let mut nearest: Vec<(f32, Entity, Vec2)> = Vec::with_capacity(k + 1);
+
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let center = self.cell_coordinates(position);
+        let max_ring = (max_radius / self.cell_size).ceil() as i32 + 1;
+        let max_radius_squared = max_radius * max_radius;
+
+        for ring in 0..=max_ring {
+            for cell in ring_cells(center, ring) {
+                let Some(entries) = self.cells.get(&cell) else {
+                    continue;
+                };
+
+                for &(entity, other_position) in entries {
+                    let distance_squared = position.distance_squared(other_position);
+
+                    if distance_squared > max_radius_squared
+                        || (nearest.len() == k && distance_squared >= nearest[k - 1].0)
+                    {
+                        continue;
+                    }
+
+                    let index = nearest.partition_point(|(other, ..)| *other <= distance_squared);
+                    nearest.insert(index, (distance_squared, entity, other_position));
+                    nearest.truncate(k);
+                }
+            }
+
+            // Everything in the next ring is at least `ring` cells away from `position`.
+            let next_ring_distance = ring as f32 * self.cell_size;
+            if nearest.len() == k && nearest[k - 1].0 <= next_ring_distance * next_ring_distance {
+                break;
+            }
+        }
+
+        nearest
+            .into_iter()
+            .map(|(_, entity, other_position)| (entity, other_position))
+            .collect()
+    }
+
+    fn cell_coordinates(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+}
+
+/// Cells on the square ring `ring` cells away from `center`.
+fn ring_cells(center: IVec2, ring: i32) -> impl Iterator<Item = IVec2> {
+    (-ring..=ring)
+        .flat_map(move |y| (-ring..=ring).map(move |x| IVec2::new(x, y)))
+        .filter(move |offset| offset.x.abs() == ring || offset.y.abs() == ring)
+        .map(move |offset| center + offset)
+}
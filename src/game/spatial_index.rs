@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::playing;
+
+use super::{
+    combat::Projectile, enemy::Behavior, level::Building, power_up::PowerUp,
+    spatial_hash::SpatialHash, Enemy, TILE_SIZE,
+};
+
+pub(super) struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>();
+
+        // Rebuilt before `FixedUpdate` so every gameplay system sees this tick's positions.
+        app.add_systems(FixedPreUpdate, rebuild_spatial_index.run_if(playing()));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialLayer {
+    /// Moving enemy units, excluding buildings.
+    Enemies,
+    Buildings,
+    Projectiles,
+    PowerUps,
+}
+
+impl SpatialLayer {
+    const COUNT: usize = 4;
+}
+
+/// Positions of gameplay entities bucketed by kind, shared by every proximity query.
+#[derive(Resource)]
+pub struct SpatialIndex {
+    layers: [SpatialHash; SpatialLayer::COUNT],
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self {
+            layers: std::array::from_fn(|_| SpatialHash::new(TILE_SIZE.x * 4.)),
+        }
+    }
+}
+
+impl SpatialIndex {
+    pub fn layer(&self, layer: SpatialLayer) -> &SpatialHash {
+        &self.layers[layer as usize]
+    }
+
+    /// Iterates over the entities of `layer` within `radius` of `position`.
+    pub fn query_radius(
+        &self,
+        layer: SpatialLayer,
+        position: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        self.layer(layer).query_radius(position, radius)
+    }
+
+    /// Returns up to `k` entities of `layer` within `max_radius` of `position`, closest first.
+    pub fn nearest_k(
+        &self,
+        layer: SpatialLayer,
+        position: Vec2,
+        k: usize,
+        max_radius: f32,
+    ) -> Vec<(Entity, Vec2)> {
+        self.layer(layer).nearest_k(position, k, max_radius)
+    }
+
+    fn layer_mut(&mut self, layer: SpatialLayer) -> &mut SpatialHash {
+        &mut self.layers[layer as usize]
+    }
+}
+
+fn rebuild_spatial_index(
+    mut spatial_index: ResMut<SpatialIndex>,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, With<Behavior>)>,
+    building_query: Query<(Entity, &Transform), With<Building>>,
+    projectile_query: Query<(Entity, &Transform), With<Projectile>>,
+    power_up_query: Query<(Entity, &Transform), With<PowerUp>>,
+) {
+    rebuild_layer(
+        spatial_index.layer_mut(SpatialLayer::Enemies),
+        enemy_query.iter(),
+    );
+    rebuild_layer(
+        spatial_index.layer_mut(SpatialLayer::Buildings),
+        building_query.iter(),
+    );
+    rebuild_layer(
+        spatial_index.layer_mut(SpatialLayer::Projectiles),
+        projectile_query.iter(),
+    );
+    rebuild_layer(
+        spatial_index.layer_mut(SpatialLayer::PowerUps),
+        power_up_query.iter(),
+    );
+}
+
+fn rebuild_layer<'a>(
+    spatial_hash: &mut SpatialHash,
+    entities: impl Iterator<Item = (Entity, &'a Transform)>,
+) {
+    spatial_hash.clear();
+
+    for (entity, transform) in entities {
+        spatial_hash.insert(entity, transform.translation.xy());
+    }
+}
@@ -0,0 +1,530 @@
+use std::{collections::HashMap, fmt, time::Duration};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+    render::view::RenderLayers,
+};
+use serde::Deserialize;
+
+use crate::{physics::Speed, playing, AppState};
+
+use super::{
+    damage::{Damage, DamageEvent, DamageType},
+    death::Dying,
+    entity_pool::Pooled,
+};
+
+/// Names of the effects in `effects/status.effects.ron` the game applies.
+pub const BURNING: &str = "burning";
+pub const SLOWED: &str = "slowed";
+pub const STUNNED: &str = "stunned";
+pub const FRIGHTENED: &str = "frightened";
+pub const NETTED: &str = "netted";
+pub const POISONED: &str = "poisoned";
+pub const SHIELDED: &str = "shielded";
+pub const COOLED: &str = "cooled";
+
+const MARKER_SIZE: f32 = 4.;
+const MARKER_HEIGHT: f32 = 28.;
+
+pub(super) struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StatusEffectSet>();
+        app.init_asset_loader::<StatusEffectSetLoader>();
+
+        app.add_event::<ApplyStatusEffect>();
+        app.add_event::<StatusEffectExpired>();
+
+        app.add_systems(Startup, load_status_effects);
+        app.add_systems(
+            FixedUpdate,
+            (
+                apply_status_effects,
+                tick_status_effects,
+                update_status_modifiers,
+            )
+                .chain()
+                .run_if(playing()),
+        );
+
+        app.add_observer(on_remove_status_effects);
+        app.add_observer(forget_pooled_sources);
+    }
+}
+
+/// Every status effect in the game, loaded from an `.effects.ron` file.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct StatusEffectSet {
+    effects: HashMap<String, StatusEffectDefinition>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct StatusEffectDefinition {
+    /// Seconds the effect lasts after being applied.
+    duration: f32,
+    #[serde(default)]
+    stacking: Stacking,
+    #[serde(default)]
+    periodic_damage: Option<PeriodicDamage>,
+    /// Multiplies the speed of the entity, once per stack.
+    #[serde(default = "unchanged")]
+    speed: f32,
+    /// Multiplies the damage the entity deals, once per stack.
+    #[serde(default = "unchanged")]
+    damage_dealt: f32,
+    /// Multiplies the damage of each type the entity takes, once per stack.
+    #[serde(default)]
+    damage_taken: Vec<(DamageType, f32)>,
+    /// Multiplies the heat the dragon builds by breathing, once per stack.
+    #[serde(default = "unchanged")]
+    heat_built: f32,
+    /// Multiplies how fast the dragon's fire comes back, once per stack.
+    #[serde(default = "unchanged")]
+    fire_regeneration: f32,
+    #[serde(default)]
+    control: Option<Control>,
+    /// Color of the marker shown over the entity while the effect lasts.
+    #[serde(default)]
+    color: Option<(f32, f32, f32)>,
+}
+
+fn unchanged() -> f32 {
+    1.
+}
+
+/// What applying an effect the entity already has does.
+#[derive(Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+enum Stacking {
+    /// Starts the duration over.
+    #[default]
+    Refresh,
+    /// Adds the duration to what is left of it.
+    Extend,
+    /// Adds a stack, up to the given maximum, and starts the duration over.
+    Stack(u32),
+}
+
+/// Damage dealt every `interval` seconds while the effect lasts, once per stack.
+#[derive(Deserialize, Clone, Copy, Debug)]
+struct PeriodicDamage {
+    interval: f32,
+    amount: i16,
+    damage_type: DamageType,
+}
+
+/// Takes over how an affected enemy moves and attacks.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Neither moves nor attacks.
+    Stunned,
+    /// Runs away from the player instead of attacking.
+    Frightened,
+}
+
+#[derive(Resource)]
+struct StatusEffectLibrary(Handle<StatusEffectSet>);
+
+/// Asks for the effect named `effect` to be applied to `target`.
+#[derive(Event)]
+pub struct ApplyStatusEffect {
+    pub target: Entity,
+    pub effect: &'static str,
+    /// Whoever applied the effect, credited with its periodic damage.
+    pub source: Option<Entity>,
+}
+
+impl ApplyStatusEffect {
+    pub fn new(target: Entity, effect: &'static str, source: Option<Entity>) -> Self {
+        Self {
+            target,
+            effect,
+            source,
+        }
+    }
+}
+
+/// Sent when an effect ran for its whole duration, but not when it was removed early.
+#[derive(Event)]
+pub struct StatusEffectExpired {
+    pub entity: Entity,
+    pub effect: &'static str,
+}
+
+impl StatusEffectExpired {
+    pub fn new(entity: Entity, effect: &'static str) -> Self {
+        Self { entity, effect }
+    }
+}
+
+struct ActiveStatusEffect {
+    name: &'static str,
+    definition: StatusEffectDefinition,
+    stacks: u32,
+    timer: Timer,
+    periodic_timer: Option<Timer>,
+    source: Option<Entity>,
+}
+
+/// Effects currently applied to the entity.
+#[derive(Component, Default)]
+pub struct StatusEffects(Vec<ActiveStatusEffect>);
+
+impl StatusEffects {
+    /// Seconds since the effect was last applied, if the entity has it.
+    pub fn elapsed_secs(&self, effect: &str) -> Option<f32> {
+        self.get(effect).map(|active| active.timer.elapsed_secs())
+    }
+
+    /// Removes the effect without it counting as expired.
+    pub fn remove(&mut self, effect: &str) {
+        self.0.retain(|active| active.name != effect);
+    }
+
+    fn get(&self, effect: &str) -> Option<&ActiveStatusEffect> {
+        self.0.iter().find(|active| active.name == effect)
+    }
+
+    fn apply(
+        &mut self,
+        name: &'static str,
+        definition: &StatusEffectDefinition,
+        source: Option<Entity>,
+    ) {
+        let Some(active) = self.0.iter_mut().find(|active| active.name == name) else {
+            self.0.push(ActiveStatusEffect {
+                name,
+                definition: definition.clone(),
+                stacks: 1,
+                timer: Timer::from_seconds(definition.duration, TimerMode::Once),
+                periodic_timer: definition
+                    .periodic_damage
+                    .map(|periodic| Timer::from_seconds(periodic.interval, TimerMode::Repeating)),
+                source,
+            });
+            return;
+        };
+
+        match definition.stacking {
+            Stacking::Refresh => active.timer.reset(),
+            Stacking::Extend => {
+                let remaining = active.timer.remaining();
+                active
+                    .timer
+                    .set_duration(remaining + Duration::from_secs_f32(definition.duration));
+                active.timer.reset();
+            }
+            Stacking::Stack(max_stacks) => {
+                active.stacks = (active.stacks + 1).min(max_stacks);
+                active.timer.reset();
+            }
+        }
+
+        if source.is_some() {
+            active.source = source;
+        }
+    }
+
+    fn modifiers(&self) -> StatusModifiers {
+        let mut modifiers = StatusModifiers::default();
+
+        for active in &self.0 {
+            let definition = &active.definition;
+            let stacks = active.stacks as i32;
+
+            modifiers.speed *= definition.speed.powi(stacks);
+            modifiers.damage_dealt *= definition.damage_dealt.powi(stacks);
+            modifiers.heat_built *= definition.heat_built.powi(stacks);
+            modifiers.fire_regeneration *= definition.fire_regeneration.powi(stacks);
+
+            for &(damage_type, multiplier) in &definition.damage_taken {
+                modifiers.damage_taken[damage_type as usize] *= multiplier.powi(stacks);
+            }
+
+            // Being stunned outweighs anything else that wants to move the entity.
+            if modifiers.control != Some(Control::Stunned) {
+                modifiers.control = definition.control.or(modifiers.control);
+            }
+        }
+
+        modifiers
+    }
+}
+
+/// Combined effect of everything in [`StatusEffects`], kept up to date whenever it changes.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct StatusModifiers {
+    speed: f32,
+    damage_dealt: f32,
+    damage_taken: [f32; DamageType::COUNT],
+    heat_built: f32,
+    fire_regeneration: f32,
+    control: Option<Control>,
+}
+
+impl Default for StatusModifiers {
+    fn default() -> Self {
+        Self {
+            speed: 1.,
+            damage_dealt: 1.,
+            damage_taken: [1.; DamageType::COUNT],
+            heat_built: 1.,
+            fire_regeneration: 1.,
+            control: None,
+        }
+    }
+}
+
+impl StatusModifiers {
+    pub fn damage_dealt(&self) -> f32 {
+        self.damage_dealt
+    }
+
+    pub fn damage_taken(&self, damage_type: DamageType) -> f32 {
+        self.damage_taken[damage_type as usize]
+    }
+
+    pub fn heat_built(&self) -> f32 {
+        self.heat_built
+    }
+
+    pub fn fire_regeneration(&self) -> f32 {
+        self.fire_regeneration
+    }
+
+    pub fn control(&self) -> Option<Control> {
+        self.control
+    }
+}
+
+/// Speed the entity had before any effect changed it.
+#[derive(Component)]
+struct BaseSpeed(f32);
+
+/// Small colored square over an entity for each of its visible effects.
+#[derive(Component)]
+struct StatusEffectMarker;
+
+fn load_status_effects(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(StatusEffectLibrary(
+        asset_server.load("effects/status.effects.ron"),
+    ));
+}
+
+fn apply_status_effects(
+    mut apply_status_effect_event_reader: EventReader<ApplyStatusEffect>,
+    mut query: Query<&mut StatusEffects, Without<Dying>>,
+    library: Res<StatusEffectLibrary>,
+    status_effect_sets: Res<Assets<StatusEffectSet>>,
+) {
+    let Some(status_effect_set) = status_effect_sets.get(&library.0) else {
+        return;
+    };
+
+    for &ApplyStatusEffect {
+        target,
+        effect,
+        source,
+    } in apply_status_effect_event_reader.read()
+    {
+        let Some(definition) = status_effect_set.effects.get(effect) else {
+            warn!("unknown status effect: {effect}");
+            continue;
+        };
+
+        if let Ok(mut status_effects) = query.get_mut(target) {
+            status_effects.apply(effect, definition, source);
+        }
+    }
+}
+
+fn tick_status_effects(
+    mut damage_event_writer: EventWriter<DamageEvent>,
+    mut status_effect_expired_event_writer: EventWriter<StatusEffectExpired>,
+    mut query: Query<(Entity, &Transform, &mut StatusEffects)>,
+    time: Res<Time>,
+) {
+    for (entity, transform, mut status_effects) in &mut query {
+        if status_effects.0.is_empty() {
+            continue;
+        }
+
+        // Ticking timers alone doesn't change what the effects do to the entity.
+        let mut expired = false;
+
+        for active in &mut status_effects.bypass_change_detection().0 {
+            active.timer.tick(time.delta());
+
+            if let (Some(periodic_damage), Some(periodic_timer)) = (
+                active.definition.periodic_damage,
+                active.periodic_timer.as_mut(),
+            ) {
+                let damage = Damage::new(
+                    periodic_damage.amount * active.stacks as i16,
+                    periodic_damage.damage_type,
+                );
+
+                for _ in 0..periodic_timer.tick(time.delta()).times_finished_this_tick() {
+                    damage_event_writer.write(DamageEvent::new(
+                        active.source.unwrap_or(entity),
+                        entity,
+                        damage,
+                        transform.translation.xy(),
+                    ));
+                }
+            }
+
+            if active.timer.finished() {
+                status_effect_expired_event_writer
+                    .write(StatusEffectExpired::new(entity, active.name));
+                expired = true;
+            }
+        }
+
+        if expired {
+            status_effects.0.retain(|active| !active.timer.finished());
+        }
+    }
+}
+
+fn update_status_modifiers(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &StatusEffects,
+            Option<&mut Speed>,
+            Option<&BaseSpeed>,
+            Option<&Children>,
+            Option<&RenderLayers>,
+        ),
+        Changed<StatusEffects>,
+    >,
+    marker_query: Query<Entity, With<StatusEffectMarker>>,
+) {
+    for (entity, status_effects, speed, base_speed, children, render_layers) in &mut query {
+        for marker in marker_query.iter_many(children.into_iter().flatten()) {
+            commands.entity(marker).despawn();
+        }
+
+        if status_effects.0.is_empty() {
+            if let (Some(mut speed), Some(base_speed)) = (speed, base_speed) {
+                **speed = base_speed.0;
+            }
+            commands
+                .entity(entity)
+                .try_remove::<(StatusModifiers, BaseSpeed)>();
+            continue;
+        }
+
+        let modifiers = status_effects.modifiers();
+
+        if let Some(mut speed) = speed {
+            let base = base_speed.map_or(**speed, |base_speed| base_speed.0);
+            **speed = base * modifiers.speed;
+            if base_speed.is_none() {
+                commands.entity(entity).try_insert(BaseSpeed(base));
+            }
+        }
+
+        commands.entity(entity).try_insert(modifiers);
+
+        let colors = status_effects
+            .0
+            .iter()
+            .filter_map(|active| active.definition.color)
+            .collect::<Vec<_>>();
+        let first_offset = (colors.len() as f32 - 1.) * MARKER_SIZE * -0.5;
+
+        for (index, (red, green, blue)) in colors.into_iter().enumerate() {
+            commands.entity(entity).with_child((
+                StatusEffectMarker,
+                Sprite::from_color(Color::srgb(red, green, blue), Vec2::splat(MARKER_SIZE)),
+                Transform::from_xyz(
+                    first_offset + index as f32 * MARKER_SIZE * 1.5,
+                    MARKER_HEIGHT,
+                    1.,
+                ),
+                render_layers.cloned().unwrap_or_default(),
+                StateScoped(AppState::GameOver),
+            ));
+        }
+    }
+}
+
+/// Markers would otherwise outlive the effects of entities handed back to a pool.
+fn on_remove_status_effects(
+    trigger: Trigger<OnRemove, StatusEffects>,
+    mut commands: Commands,
+    query: Query<&Children>,
+    marker_query: Query<Entity, With<StatusEffectMarker>>,
+) {
+    let Ok(children) = query.get(trigger.target()) else {
+        return;
+    };
+
+    for marker in marker_query.iter_many(children) {
+        commands.entity(marker).try_despawn();
+    }
+}
+
+/// A pooled source comes back as a different unit, which mustn't be credited for what the
+/// old one did.
+fn forget_pooled_sources(trigger: Trigger<OnAdd, Pooled>, mut query: Query<&mut StatusEffects>) {
+    let source = Some(trigger.target());
+
+    for mut status_effects in &mut query {
+        for active in &mut status_effects.bypass_change_detection().0 {
+            if active.source == source {
+                active.source = None;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct StatusEffectSetLoader;
+
+#[derive(Debug)]
+enum StatusEffectSetLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for StatusEffectSetLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read status effects: {error}"),
+            Self::Ron(error) => write!(f, "could not parse status effects: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for StatusEffectSetLoaderError {}
+
+impl AssetLoader for StatusEffectSetLoader {
+    type Asset = StatusEffectSet;
+    type Settings = ();
+    type Error = StatusEffectSetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(StatusEffectSetLoaderError::Io)?;
+
+        ron::de::from_bytes(&bytes).map_err(StatusEffectSetLoaderError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["effects.ron"]
+    }
+}
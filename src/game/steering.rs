@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use super::{
+    spatial_index::{SpatialIndex, SpatialLayer},
+    HALF_TILE_SIZE, TILE_SIZE,
+};
+
+/// Most neighbors a unit looks at when pulling towards its group.
+const COHESION_NEIGHBORS: usize = 6;
+
+/// Weights and radii used to blend the steering forces applied to moving enemies.
+#[derive(Resource)]
+pub struct SteeringSettings {
+    pub seek_weight: f32,
+    pub separation_radius: f32,
+    pub separation_weight: f32,
+    pub cohesion_radius: f32,
+    pub cohesion_weight: f32,
+    pub avoidance_radius: f32,
+    pub avoidance_weight: f32,
+    /// Fraction of the follow distance enemies tolerate before backing away from the player.
+    pub ring_tolerance: f32,
+}
+
+impl Default for SteeringSettings {
+    fn default() -> Self {
+        Self {
+            seek_weight: 1.0,
+            separation_radius: TILE_SIZE.x * 1.5,
+            separation_weight: 1.5,
+            cohesion_radius: TILE_SIZE.x * 4.,
+            cohesion_weight: 0.2,
+            avoidance_radius: TILE_SIZE.x * 2.,
+            avoidance_weight: 2.0,
+            ring_tolerance: 0.8,
+        }
+    }
+}
+
+/// Pushes away from neighbors, stronger the closer they are.
+pub fn separation(
+    entity: Entity,
+    position: Vec2,
+    spatial_index: &SpatialIndex,
+    radius: f32,
+) -> Vec2 {
+    spatial_index
+        .query_radius(SpatialLayer::Enemies, position, radius)
+        .filter(|(other_entity, _)| *other_entity != entity)
+        .fold(Vec2::ZERO, |force, (other_entity, other_position)| {
+            let offset = position - other_position;
+            let distance = offset.length();
+            // Units sharing a position get a deterministic nudge so they can split apart.
+            let away = offset.try_normalize().unwrap_or_else(|| {
+                Vec2::from_angle(other_entity.index().wrapping_sub(entity.index()) as f32)
+            });
+
+            force + away * (1. - distance / radius)
+        })
+}
+
+/// Pulls towards the center of the closest neighbors so groups move as a crowd.
+pub fn cohesion(entity: Entity, position: Vec2, spatial_index: &SpatialIndex, radius: f32) -> Vec2 {
+    // One extra neighbor since the unit itself is the closest entry.
+    let (sum, count) = spatial_index
+        .nearest_k(
+            SpatialLayer::Enemies,
+            position,
+            COHESION_NEIGHBORS + 1,
+            radius,
+        )
+        .into_iter()
+        .filter(|(other_entity, _)| *other_entity != entity)
+        .fold((Vec2::ZERO, 0), |(sum, count), (_, other_position)| {
+            (sum + other_position, count + 1)
+        });
+
+    if count == 0 {
+        return Vec2::ZERO;
+    }
+
+    ((sum / count as f32) - position).normalize_or_zero()
+}
+
+/// Pushes away from nearby obstacles and slides along them in the desired direction.
+pub fn obstacle_avoidance(
+    position: Vec2,
+    desired_direction: Vec2,
+    spatial_index: &SpatialIndex,
+    radius: f32,
+) -> Vec2 {
+    let radius = radius + HALF_TILE_SIZE.x;
+
+    spatial_index
+        .query_radius(SpatialLayer::Buildings, position, radius)
+        .fold(Vec2::ZERO, |force, (_, obstacle_position)| {
+            let offset = position - obstacle_position;
+            let Some(away) = offset.try_normalize() else {
+                return force;
+            };
+            let tangent = if away.perp().dot(desired_direction) >= 0. {
+                away.perp()
+            } else {
+                -away.perp()
+            };
+
+            force + (away + tangent) * (1. - offset.length() / radius)
+        })
+}
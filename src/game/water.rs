@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_enoki::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{camera::RenderLayer, playing, AppState};
+
+use super::{
+    combat::{OnFire, EXTINGUISHED_HEALTH_RATIO},
+    death::{DeathCause, DeathEvent, Dying, Rescued},
+    enemy::Behavior,
+    level::{LevelMatrix, Tile},
+    resource_pool::{Fire, Health, ResourcePool},
+    status_effect::{ApplyStatusEffect, StatusEffects, BURNING, SLOWED},
+    Player,
+};
+
+/// How many tiles away a burning unit looks for water before giving up and running wild.
+const WATER_SEARCH_DISTANCE: usize = 12;
+/// Units panic for a moment after catching fire before water can put it out.
+const MIN_BURN_SECONDS: f32 = 1.;
+
+pub(super) struct WaterPlugin;
+
+impl Plugin for WaterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                seek_water,
+                extinguish_in_water,
+                finish_fleeing_units,
+                slow_wading_units,
+            )
+                .run_if(playing()),
+        );
+    }
+}
+
+/// Burning units head for the closest water they can find instead of running at random.
+fn seek_water(
+    mut burning_query: Query<(&Transform, &mut Behavior), Added<OnFire>>,
+    level_matrix: Res<LevelMatrix>,
+) {
+    for (transform, mut behavior) in &mut burning_query {
+        if let Some(target) = level_matrix.nearest_tile(
+            transform.translation.xy(),
+            Tile::Water,
+            WATER_SEARCH_DISTANCE,
+        ) {
+            *behavior = Behavior::SeekWater { target };
+        }
+    }
+}
+
+fn extinguish_in_water(
+    mut commands: Commands,
+    mut burning_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut ResourcePool<Health>,
+            &StatusEffects,
+        ),
+        (With<OnFire>, With<Behavior>, Without<Dying>),
+    >,
+    level_matrix: Res<LevelMatrix>,
+    asset_server: Res<AssetServer>,
+) {
+    for (entity, transform, mut health, status_effects) in &mut burning_query {
+        let position = transform.translation.xy();
+
+        if burn_seconds(status_effects) < MIN_BURN_SECONDS
+            || level_matrix.tile_at(position) != Some(Tile::Water)
+        {
+            continue;
+        }
+
+        let extinguished_health = ((health.max() as f32 * EXTINGUISHED_HEALTH_RATIO) as i16).max(1);
+
+        if health.is_empty() {
+            commands.entity(entity).insert(Rescued);
+        }
+        health.set_current(extinguished_health);
+        commands.entity(entity).remove::<OnFire>();
+        commands.spawn((
+            ParticleSpawner::default(),
+            ParticleEffectHandle(asset_server.load("vfx/steam.ron")),
+            OneShot::Despawn,
+            RenderLayers::layer(RenderLayer::Ground.into()),
+            Transform::from_translation(position.extend(10.0)),
+            StateScoped(AppState::GameOver),
+        ));
+    }
+}
+
+/// Keeping the breath on a unit running for water burns it down before it gets there.
+/// A unit touching several fires at once is only finished once.
+fn finish_fleeing_units(
+    mut commands: Commands,
+    mut death_event_writer: EventWriter<DeathEvent>,
+    fleeing_query: Query<
+        (&Transform, &ResourcePool<Health>, &Behavior, &StatusEffects),
+        (With<OnFire>, Without<Dying>),
+    >,
+    fire_query: Query<Entity, With<Fire>>,
+    player_entity: Single<Entity, With<Player>>,
+    rapier_context: ReadRapierContext,
+    mut in_fire: Local<HashSet<Entity>>,
+) {
+    let Ok(rapier_context) = rapier_context.single() else {
+        return;
+    };
+    in_fire.clear();
+
+    for entity in &fire_query {
+        for (entity1, entity2, intersecting) in rapier_context.intersection_pairs_with(entity) {
+            let other_entity = if entity1 == entity { entity2 } else { entity1 };
+
+            if intersecting && fleeing_query.contains(other_entity) {
+                in_fire.insert(other_entity);
+            }
+        }
+    }
+
+    for &entity in in_fire.iter() {
+        let Ok((transform, health, behavior, status_effects)) = fleeing_query.get(entity) else {
+            continue;
+        };
+
+        if !matches!(behavior, Behavior::SeekWater { .. })
+            || burn_seconds(status_effects) < MIN_BURN_SECONDS
+        {
+            continue;
+        }
+
+        // Units whose health ran out already died when they were set alight.
+        if !health.is_empty() {
+            death_event_writer.write(DeathEvent::new(
+                entity,
+                DeathCause::Finished,
+                Some(*player_entity),
+                transform.translation.xy(),
+            ));
+        }
+        commands.entity(entity).try_insert(Dying);
+    }
+}
+
+/// Units wading through water move slower for as long as they are in it.
+fn slow_wading_units(
+    mut apply_status_effect_event_writer: EventWriter<ApplyStatusEffect>,
+    unit_query: Query<(Entity, &Transform), (With<Behavior>, Without<OnFire>)>,
+    level_matrix: Res<LevelMatrix>,
+) {
+    for (entity, transform) in &unit_query {
+        if level_matrix.tile_at(transform.translation.xy()) == Some(Tile::Water) {
+            apply_status_effect_event_writer.write(ApplyStatusEffect::new(entity, SLOWED, None));
+        }
+    }
+}
+
+fn burn_seconds(status_effects: &StatusEffects) -> f32 {
+    status_effects.elapsed_secs(BURNING).unwrap_or_default()
+}
@@ -1,14 +1,17 @@
-use std::{f32::consts::FRAC_PI_2, time::Duration};
+use std::f32::consts::FRAC_PI_2;
 
 use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
 use bevy_enhanced_input::prelude::*;
 use bevy_rapier2d::prelude::Collider;
 
 use crate::{
-    animation::AnimationTimer, camera::MainCamera, game::Player, physics::Speed, playing, AppState,
+    animation::SpriteAnimation, camera::MainCamera, game::Player, physics::Speed, playing, AppState,
 };
 use actions::*;
 
+/// Seconds the breath button has to be held before the breath starts, by which point it is fully charged.
+pub const FIRE_BREATH_HOLD_SECONDS: f32 = 0.5;
+
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
@@ -39,6 +42,34 @@ pub mod actions {
     #[derive(Debug, InputAction)]
     #[input_action(output = bool)]
     pub struct FireBreath;
+
+    #[derive(Debug, InputAction)]
+    #[input_action(output = bool)]
+    pub struct FlyHigh;
+
+    #[derive(Debug, InputAction)]
+    #[input_action(output = bool)]
+    pub struct Dive;
+
+    #[derive(Debug, InputAction)]
+    #[input_action(output = bool)]
+    pub struct TailSwipe;
+
+    #[derive(Debug, InputAction)]
+    #[input_action(output = bool)]
+    pub struct Roar;
+
+    #[derive(Debug, InputAction)]
+    #[input_action(output = bool)]
+    pub struct SelectCone;
+
+    #[derive(Debug, InputAction)]
+    #[input_action(output = bool)]
+    pub struct SelectFireball;
+
+    #[derive(Debug, InputAction)]
+    #[input_action(output = bool)]
+    pub struct SelectFireWall;
 }
 
 #[derive(SystemParam)]
@@ -59,11 +90,11 @@ impl CursorWorldPositionChecker<'_> {
 }
 
 fn player_movement(
-    player: Single<(&mut Transform, &Speed, &mut AnimationTimer, &Collider), With<Player>>,
+    player: Single<(&mut Transform, &Speed, &mut SpriteAnimation, &Collider), With<Player>>,
     cursor_world_position_checker: CursorWorldPositionChecker,
 ) {
     if let Some(cursor_position) = cursor_world_position_checker.cursor_world_position() {
-        let (mut player_transform, player_speed, mut player_animation_timer, player_collider) =
+        let (mut player_transform, player_speed, mut player_animation, player_collider) =
             player.into_inner();
         let player_position = player_transform.translation.truncate();
         let cursor_to_player_vector = cursor_position - player_position;
@@ -76,11 +107,9 @@ fn player_movement(
             if cursor_distance_to_player > player_collider.as_cuboid().unwrap().half_extents().y {
                 player_transform.translation.x += direction.x * player_speed.0 * velocity_rate;
                 player_transform.translation.y += direction.y * player_speed.0 * velocity_rate;
-                player_animation_timer.set_duration(Duration::from_secs_f32(
-                    0.2 * player_speed.0 * 0.25 * velocity_rate,
-                ));
+                player_animation.set_duration_scale(player_speed.0 * 0.25 * velocity_rate);
             } else {
-                player_animation_timer.set_duration(Duration::from_secs_f32(0.2));
+                player_animation.set_duration_scale(1.);
             }
 
             if direction != Vec2::ZERO {
@@ -109,5 +138,12 @@ fn bind_actions(
     actions
         .bind::<FireBreath>()
         .to(MouseButton::Left)
-        .with_conditions(Hold::new(0.5));
+        .with_conditions(Hold::new(FIRE_BREATH_HOLD_SECONDS));
+    actions.bind::<FlyHigh>().to(KeyCode::ShiftLeft);
+    actions.bind::<Dive>().to(KeyCode::Space);
+    actions.bind::<TailSwipe>().to(MouseButton::Right);
+    actions.bind::<Roar>().to(KeyCode::KeyR);
+    actions.bind::<SelectCone>().to(KeyCode::Digit1);
+    actions.bind::<SelectFireball>().to(KeyCode::Digit2);
+    actions.bind::<SelectFireWall>().to(KeyCode::Digit3);
 }
@@ -2,6 +2,7 @@ use bevy::{app::AppExit, color::palettes::css::ALICE_BLUE, prelude::*};
 
 use crate::{
     audio::{PlayMusicEvent, PlaybackSettings},
+    game::Difficulty,
     AppState,
 };
 
@@ -24,11 +25,23 @@ impl Plugin for MainMenuPlugin {
 #[derive(Component)]
 enum MainMenuButtonAction {
     NewGame,
+    CycleDifficulty,
     #[cfg(not(target_family = "wasm"))]
     Exit,
 }
 
-fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+struct DifficultyButtonText;
+
+fn difficulty_button_text(difficulty: Difficulty) -> String {
+    format!("Difficulty: {}", difficulty.label())
+}
+
+fn setup_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    difficulty: Res<Difficulty>,
+) {
     let font = asset_server.load("fonts/MorrisRomanAlternate-Black.ttf");
 
     commands.spawn((
@@ -45,6 +58,22 @@ fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
             Spawn(ImageNode::new(
                 asset_server.load("textures/menu_background.png"),
             )),
+            Spawn((
+                Button,
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Percent(18.),
+                    ..default()
+                },
+                BackgroundColor::from(ALICE_BLUE),
+                MainMenuButtonAction::CycleDifficulty,
+                children![(
+                    DifficultyButtonText,
+                    Text::new(difficulty_button_text(*difficulty)),
+                    TextFont::from_font(font.clone()).with_font_size(32.0),
+                    TextColor(Color::BLACK),
+                )],
+            )),
             Spawn((
                 Button,
                 Node {
@@ -83,7 +112,9 @@ fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
 fn handle_main_menu_button_interactions(
     mut exit: EventWriter<AppExit>,
     mut app_state: ResMut<NextState<AppState>>,
-    query: Query<(&Interaction, &MainMenuButtonAction), With<Button>>,
+    mut difficulty: ResMut<Difficulty>,
+    query: Query<(&Interaction, &MainMenuButtonAction), (With<Button>, Changed<Interaction>)>,
+    mut difficulty_text: Single<&mut Text, With<DifficultyButtonText>>,
 ) {
     for (interaction, main_menu_button_action) in query.iter() {
         match interaction {
@@ -95,6 +126,10 @@ fn handle_main_menu_button_interactions(
                 MainMenuButtonAction::NewGame => {
                     app_state.set(AppState::InGame);
                 }
+                MainMenuButtonAction::CycleDifficulty => {
+                    *difficulty = difficulty.next();
+                    difficulty_text.0 = difficulty_button_text(*difficulty);
+                }
             },
             Interaction::Hovered => (),
             Interaction::None => (),